@@ -0,0 +1,135 @@
+/// A deterministic acceptance rule of a move-based search, deciding
+/// whether to move from the current to a neighbouring solution
+pub trait AcceptanceCriterion: Send {
+    /// Returns the name of the criterion
+    fn name(&self) -> &'static str;
+
+    /// Resets the state of the criterion at the start of a run
+    fn reset(&mut self, initial_eval: i32);
+
+    /// Returns true if the candidate evaluation is accepted, given the
+    /// evaluations of the current and the best solution
+    fn accept(&mut self, current: i32, candidate: i32, best: i32) -> bool;
+
+    /// Updates the state after every iteration with the evaluations
+    /// of the current and the best solution
+    fn update(&mut self, _current: i32, _best: i32) {}
+}
+
+/// Great deluge: accepts candidates below a water level (and improving
+/// moves). The level starts at the initial evaluation and its gap to the
+/// best evaluation shrinks by the rain speed every iteration.
+pub struct GreatDeluge {
+    pub rain_speed: f64,
+    level: f64,
+}
+
+impl GreatDeluge {
+    // Constructor
+    pub fn new(rain_speed: f64) -> GreatDeluge {
+        GreatDeluge { rain_speed, level: 0.0 }
+    }
+}
+
+impl AcceptanceCriterion for GreatDeluge {
+    fn name(&self) -> &'static str {
+        "great-deluge"
+    }
+    fn reset(&mut self, initial_eval: i32) {
+        self.level = initial_eval as f64;
+    }
+    fn accept(&mut self, current: i32, candidate: i32, _best: i32) -> bool {
+        candidate <= current || candidate as f64 <= self.level
+    }
+    fn update(&mut self, _current: i32, best: i32) {
+        self.level -= self.rain_speed * (self.level - best as f64);
+    }
+}
+
+/// Threshold accepting: accepts candidates worse than the current
+/// solution by less than a threshold, relative to the current evaluation.
+/// The threshold decreases geometrically every iteration.
+pub struct ThresholdAccepting {
+    pub initial_threshold: f64,
+    pub decay: f64,
+    threshold: f64,
+}
+
+impl ThresholdAccepting {
+    // Constructor
+    pub fn new(initial_threshold: f64, decay: f64) -> ThresholdAccepting {
+        ThresholdAccepting {
+            initial_threshold,
+            decay,
+            threshold: initial_threshold,
+        }
+    }
+}
+
+impl AcceptanceCriterion for ThresholdAccepting {
+    fn name(&self) -> &'static str {
+        "threshold-accepting"
+    }
+    fn reset(&mut self, _initial_eval: i32) {
+        self.threshold = self.initial_threshold;
+    }
+    fn accept(&mut self, current: i32, candidate: i32, _best: i32) -> bool {
+        ((candidate - current) as f64) <= self.threshold * (current as f64).abs()
+    }
+    fn update(&mut self, _current: i32, _best: i32) {
+        self.threshold *= self.decay;
+    }
+}
+
+/// Record-to-record travel: accepts candidates within a deviation
+/// from the best evaluation (the record), relative to it
+pub struct RecordToRecordTravel {
+    pub deviation: f64,
+}
+
+impl AcceptanceCriterion for RecordToRecordTravel {
+    fn name(&self) -> &'static str {
+        "rrt"
+    }
+    fn reset(&mut self, _initial_eval: i32) {}
+    fn accept(&mut self, current: i32, candidate: i32, best: i32) -> bool {
+        candidate <= current || candidate as f64 <= best as f64 + self.deviation * (best as f64).abs()
+    }
+}
+
+/// Late acceptance hill climbing: accepts candidates not worse than
+/// the current solution or than the current solution `history_length`
+/// iterations ago
+pub struct LateAcceptance {
+    pub history_length: usize,
+    history: Vec<i32>,
+    iteration: usize,
+}
+
+impl LateAcceptance {
+    // Constructor
+    pub fn new(history_length: usize) -> LateAcceptance {
+        LateAcceptance {
+            history_length: history_length.max(1),
+            history: vec![],
+            iteration: 0,
+        }
+    }
+}
+
+impl AcceptanceCriterion for LateAcceptance {
+    fn name(&self) -> &'static str {
+        "lahc"
+    }
+    fn reset(&mut self, initial_eval: i32) {
+        self.history = vec![initial_eval; self.history_length];
+        self.iteration = 0;
+    }
+    fn accept(&mut self, current: i32, candidate: i32, _best: i32) -> bool {
+        candidate <= current || candidate <= self.history[self.iteration % self.history_length]
+    }
+    fn update(&mut self, current: i32, _best: i32) {
+        self.history[self.iteration % self.history_length] = current;
+        self.iteration += 1;
+    }
+}
@@ -0,0 +1,39 @@
+use qap_local_search::catalogue::{Catalogue, Selection};
+
+use std::env;
+use std::path::Path;
+
+/// Folder with instances. This folder should be inside ./data
+const DATA_FOLDER: &str = "qapdatsol";
+
+/// Lists the instances of the data folder selected by the arguments,
+/// e.g. `tai*a n<=60`, with their family, class, category, size and optimum
+fn main() {
+    let text = env::args().skip(1).collect::<Vec<String>>().join(" ");
+    let selection = match Selection::parse(&text) {
+        Ok(selection) => selection,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return;
+        }
+    };
+    let data_path = Path::new(".").join("data").join(DATA_FOLDER).to_string_lossy().to_string();
+    match Catalogue::scan(&data_path) {
+        Ok(catalogue) => {
+            println!("| instance | family | class | category | n | optimum |");
+            println!("|---|---|---|---|---|---|");
+            for entry in catalogue.select(&selection) {
+                println!(
+                    "| {} | {} | {} | {} | {} | {} |",
+                    entry.name,
+                    entry.family,
+                    entry.class,
+                    entry.category.map(|c| c.to_string()).unwrap_or_default(),
+                    entry.n,
+                    entry.optimum.map(|o| o.to_string()).unwrap_or_default()
+                );
+            }
+        }
+        Err(err) => eprintln!("Error: {}", err),
+    }
+}
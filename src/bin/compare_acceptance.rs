@@ -0,0 +1,27 @@
+use qap_local_search::experiment::{run_comparison, ComparisonSettings};
+use qap_local_search::solvers::registry::{build_solver, default_parameters};
+
+/// Instance file names
+const INSTANCES: [&str; 6] = ["nug15.dat", "els19.dat", "had20.dat", "bur26c.dat", "nug30.dat", "tai60a.dat"];
+
+/// Simulated annealing and its deterministic cousins, all
+/// sampling the swap neighbourhood in the same way
+const SOLVERS: [&str; 5] = ["sa", "great-deluge", "threshold-accepting", "rrt", "lahc"];
+
+/// How many times the experiments should be ran (for statistics)
+const NRUNS: usize = 20;
+
+/// Compares simulated annealing with great deluge, threshold accepting,
+/// record-to-record travel and late acceptance hill climbing: quality,
+/// time, evaluated moves (iterations) and accepted moves (updates), with
+/// Mann-Whitney tests of the final evaluations
+fn main() {
+    let settings = ComparisonSettings {
+        name: "compare_acceptance".to_string(),
+        n_runs: NRUNS,
+        ..ComparisonSettings::default()
+    };
+    run_comparison(&settings, &INSTANCES, &SOLVERS, |solver_name, problem| {
+        build_solver(solver_name, problem, &default_parameters(solver_name))
+    });
+}
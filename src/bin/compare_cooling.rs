@@ -0,0 +1,45 @@
+use qap_local_search::cooling::{build_cooling_schedule, StoppingRule, COOLING_NAMES};
+use qap_local_search::experiment::{run_comparison, ComparisonSettings};
+use qap_local_search::solvers::simulated_annealing_solver::SASolver;
+
+/// Instance file names
+const INSTANCES: [&str; 5] = ["nug15.dat", "had20.dat", "bur26c.dat", "nug30.dat", "tai60a.dat"];
+
+/// The cooling factor of the geometric schedules
+const TEMP_MUL: f32 = 0.9;
+/// The chain length multiplier of a frozen search
+const ITER_MUL: usize = 8;
+/// The chain length is the number of swaps divided by L_DIV
+const L_DIV: i32 = 4;
+/// The maximum number of chains of every schedule, the logarithmic
+/// schedule never freezes and stops by it
+const MAX_LEVELS: usize = 1000;
+
+/// How many times the experiments should be ran (for statistics)
+const NRUNS: usize = 10;
+
+/// Compares the cooling schedules of simulated annealing with the
+/// same chain length and stopping rule: quality, time and evaluated
+/// moves, with Mann-Whitney tests of the final evaluations
+fn main() {
+    let settings = ComparisonSettings {
+        name: "compare_cooling".to_string(),
+        n_runs: NRUNS,
+        ..ComparisonSettings::default()
+    };
+    let labels: Vec<String> = COOLING_NAMES.iter().map(|name| format!("sa-{}", name)).collect();
+    let labels: Vec<&str> = labels.iter().map(|label| label.as_str()).collect();
+    run_comparison(&settings, &INSTANCES, &labels, |label, problem| {
+        let cooling_name = label.trim_start_matches("sa-");
+        let cooling = build_cooling_schedule(cooling_name, TEMP_MUL).ok_or(format!("unknown schedule {}", cooling_name))?;
+        let mut solver = SASolver::new(problem, TEMP_MUL, ITER_MUL, L_DIV);
+        solver.compute_initial_temperature();
+        solver.set_cooling_schedule(cooling);
+        solver.set_stopping_rule(StoppingRule {
+            no_improvement_chains: ITER_MUL,
+            max_levels: Some(MAX_LEVELS),
+            ..StoppingRule::default()
+        });
+        Ok(Box::new(solver))
+    });
+}
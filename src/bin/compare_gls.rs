@@ -0,0 +1,35 @@
+use qap_local_search::experiment::{run_comparison, ComparisonSettings};
+use qap_local_search::solvers::registry::{build_solver, default_parameters};
+
+/// Instance file names: structured esc and bur instances
+const INSTANCES: [&str; 8] = [
+    "esc16c.dat",
+    "esc32e.dat",
+    "esc32g.dat",
+    "esc64a.dat",
+    "esc128.dat",
+    "bur26a.dat",
+    "bur26d.dat",
+    "bur26g.dat",
+];
+
+/// Guided local search against the plain first-improvement descent
+/// it builds on and the metaheuristics escaping local optima otherwise
+const SOLVERS: [&str; 5] = ["first-improvement", "sa", "tabu", "vns", "gls"];
+
+/// How many times the experiments should be ran (for statistics)
+const NRUNS: usize = 10;
+
+/// Compares guided local search with other local searches on structured
+/// instances where descents stall in deep local optima: quality, time,
+/// iterations and updates, with Mann-Whitney tests of the final evaluations
+fn main() {
+    let settings = ComparisonSettings {
+        name: "compare_gls".to_string(),
+        n_runs: NRUNS,
+        ..ComparisonSettings::default()
+    };
+    run_comparison(&settings, &INSTANCES, &SOLVERS, |solver_name, problem| {
+        build_solver(solver_name, problem, &default_parameters(solver_name))
+    });
+}
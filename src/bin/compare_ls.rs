@@ -0,0 +1,36 @@
+use qap_local_search::experiment::{run_comparison, ComparisonSettings};
+use qap_local_search::solvers::registry::{build_solver, default_parameters};
+
+/// Instance file names
+const INSTANCES: [&str; 8] = [
+    "nug15.dat",
+    "els19.dat",
+    "had20.dat",
+    "bur26c.dat",
+    "nug30.dat",
+    "tai60a.dat",
+    "wil100.dat",
+    "esc128.dat",
+];
+
+/// Local searches to compare: the shuffled greedy and the
+/// first improvement with don't-look bits
+const SOLVERS: [&str; 2] = ["greedy", "first-improvement"];
+
+/// How many times the experiments should be ran (for statistics)
+const NRUNS: usize = 50;
+
+/// Compares the first-improvement local search with don't-look bits
+/// against the shuffled greedy local search: quality, time, evaluated
+/// moves (iterations) and improvements (updates), with Mann-Whitney
+/// tests of the final evaluations
+fn main() {
+    let settings = ComparisonSettings {
+        name: "compare_ls".to_string(),
+        n_runs: NRUNS,
+        ..ComparisonSettings::default()
+    };
+    run_comparison(&settings, &INSTANCES, &SOLVERS, |solver_name, problem| {
+        build_solver(solver_name, problem, &default_parameters(solver_name))
+    });
+}
@@ -0,0 +1,48 @@
+use qap_local_search::experiment::{run_comparison, ComparisonSettings};
+use qap_local_search::parallel::available_threads;
+use qap_local_search::solvers::registry::{build_solver, Parameters};
+
+/// Instance file names
+const INSTANCES: [&str; 5] = ["tai12a.dat", "tai20a.dat", "tai30a.dat", "tai40a.dat", "tai60a.dat"];
+
+/// The cooling factors of simulated annealing
+const TEMP_MULS: [f64; 4] = [0.8, 0.9, 0.95, 0.99];
+
+/// How many times the experiments should be ran (for statistics)
+const NRUNS: usize = 10;
+
+/// Returns the compared configurations: simulated annealing with every
+/// cooling factor and parallel tempering on one and on all threads
+fn configurations() -> Vec<(String, &'static str, Parameters)> {
+    let mut configurations = vec![];
+    for temp_mul in TEMP_MULS {
+        let parameters = [("temp_mul".to_string(), temp_mul)].into_iter().collect();
+        configurations.push((format!("sa-{}", temp_mul), "sa", parameters));
+    }
+    let mut thread_counts = vec![1];
+    if available_threads() > 1 {
+        thread_counts.push(available_threads());
+    }
+    for n_threads in thread_counts {
+        let parameters = [("n_threads".to_string(), n_threads as f64)].into_iter().collect();
+        configurations.push((format!("pt-{}t", n_threads), "pt", parameters));
+    }
+    configurations
+}
+
+/// Compares parallel tempering with simulated annealing for several
+/// cooling factors on tai-a instances: quality, time and evaluated
+/// moves, with Mann-Whitney tests of the final evaluations
+fn main() {
+    let settings = ComparisonSettings {
+        name: "compare_pt".to_string(),
+        n_runs: NRUNS,
+        ..ComparisonSettings::default()
+    };
+    let configurations = configurations();
+    let labels: Vec<&str> = configurations.iter().map(|(label, _, _)| label.as_str()).collect();
+    run_comparison(&settings, &INSTANCES, &labels, |label, problem| {
+        let (_, solver_name, parameters) = configurations.iter().find(|(l, _, _)| l == label).unwrap();
+        build_solver(solver_name, problem, parameters)
+    });
+}
@@ -0,0 +1,44 @@
+use qap_local_search::experiment::{run_comparison, ComparisonSettings};
+use qap_local_search::solvers::vns_solver::{VNSDescent, VNSSettings, VNSSolver, VNSVariant};
+
+/// Instance file names
+const INSTANCES: [&str; 6] = ["nug15.dat", "els19.dat", "had20.dat", "bur26c.dat", "nug30.dat", "tai30a.dat"];
+
+/// Compared variants and descents of the local search stage
+const CONFIGURATIONS: [(VNSVariant, VNSDescent); 5] = [
+    (VNSVariant::Basic, VNSDescent::Steepest),
+    (VNSVariant::Basic, VNSDescent::Greedy),
+    (VNSVariant::General, VNSDescent::Steepest),
+    (VNSVariant::Skewed, VNSDescent::Steepest),
+    (VNSVariant::Skewed, VNSDescent::Greedy),
+];
+
+/// How many times the experiments should be ran (for statistics)
+const NRUNS: usize = 10;
+
+/// Compares the basic, general and skewed variable neighbourhood searches
+/// with steepest and greedy descents: quality, time, evaluated moves
+/// (iterations) and accepted shakes (updates), with Mann-Whitney tests
+/// of the final evaluations
+fn main() {
+    let settings = ComparisonSettings {
+        name: "compare_vns".to_string(),
+        n_runs: NRUNS,
+        ..ComparisonSettings::default()
+    };
+    let labels: Vec<String> = CONFIGURATIONS
+        .iter()
+        .map(|(variant, descent)| format!("{}-vns-{:?}", variant.name(), descent).to_lowercase())
+        .collect();
+    let labels: Vec<&str> = labels.iter().map(|label| label.as_str()).collect();
+    run_comparison(&settings, &INSTANCES, &labels, |label, problem| {
+        let index = labels.iter().position(|&l| l == label).unwrap();
+        let (variant, descent) = CONFIGURATIONS[index];
+        let settings = VNSSettings {
+            variant,
+            descent,
+            ..VNSSettings::default()
+        };
+        Ok(Box::new(VNSSolver::new(problem, settings)))
+    });
+}
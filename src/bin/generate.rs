@@ -0,0 +1,48 @@
+use qap_local_search::generators::{clustered, manhattan, planted, sparse, uniform};
+use qap_local_search::qap_problem::QapProblem;
+
+use std::fs;
+use std::path::Path;
+
+/// Folder with the generated instances. This folder is created inside ./data
+const GENERATED_FOLDER: &str = "generated";
+/// Instance sizes
+const SIZES: [usize; 3] = [20, 40, 80];
+/// Seed of the first instance, the seeds of the others follow
+const SEED: u64 = 0;
+
+/// Generates synthetic instances of every class and size in the QAPLIB
+/// format, with a .sln file of the known optimum of planted instances
+fn main() {
+    let folder = Path::new(".").join("data").join(GENERATED_FOLDER);
+    if let Err(err) = fs::create_dir_all(&folder) {
+        eprintln!("Error: {}", err);
+        return;
+    }
+    let mut seed = SEED;
+    for n in SIZES {
+        // Grid dimensions closest to a square
+        let rows = (1..=n).filter(|r| n % r == 0 && r * r <= n).max().unwrap_or(1);
+        let instances: Vec<(String, QapProblem)> = vec![
+            (format!("unif{}", n), uniform(n, 99, seed)),
+            (format!("clus{}", n), clustered(n, (n / 10).max(2), seed + 1)),
+            (format!("manh{}", n), manhattan(rows, n / rows, 0.5, 10, seed + 2)),
+            (format!("sprs{}", n), sparse(n, 0.1, 99, seed + 3)),
+        ];
+        let (planted_problem, optimum) = planted(n, 99, seed + 4);
+        let planted_name = format!("plnt{}", n);
+        let sln_path = folder.join(planted_name.clone() + ".sln").to_string_lossy().to_string();
+        if let Err(err) = optimum.write_sln(&sln_path) {
+            eprintln!("Error: {}", err);
+        }
+        let instances = instances.into_iter().chain([(planted_name, planted_problem)]);
+        seed += 5;
+        for (name, problem) in instances {
+            let path = folder.join(name + ".dat").to_string_lossy().to_string();
+            println!("{}", path);
+            if let Err(err) = problem.write_qaplib(&path) {
+                eprintln!("Error: {}", err);
+            }
+        }
+    }
+}
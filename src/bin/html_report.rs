@@ -0,0 +1,34 @@
+use qap_local_search::report::html::{render_html_report, HtmlReportSettings};
+use qap_local_search::report::read_results_folder;
+
+use std::fs;
+use std::path::Path;
+
+/// The name of the default folder with the csv files of the experiments
+const RESULTS_FOLDER: &str = "results";
+/// Folder with instances. This folder should be inside ./data
+const DATA_FOLDER: &str = "qapdatsol";
+/// The name of the report file, saved in the results folder
+const REPORT_FILE: &str = "report.html";
+
+/// Generates a static HTML report of the results saved in a folder
+/// (the first argument, `results` by default)
+fn main() {
+    let results_folder = std::env::args().nth(1).unwrap_or_else(|| RESULTS_FOLDER.to_string());
+    let data_path = Path::new(".").join("data").join(DATA_FOLDER).to_string_lossy().to_string();
+    match read_results_folder(&results_folder, &data_path) {
+        Ok(instances) => {
+            let settings = HtmlReportSettings {
+                title: format!("Experiment report: {}", results_folder),
+                ..HtmlReportSettings::default()
+            };
+            let html = render_html_report(&instances, &settings);
+            let path = Path::new(&results_folder).join(REPORT_FILE);
+            match fs::write(&path, html) {
+                Ok(()) => println!("Report saved to {}", path.to_string_lossy()),
+                Err(err) => eprintln!("Error: {}", err),
+            }
+        }
+        Err(err) => eprintln!("Error: {}", err),
+    }
+}
@@ -0,0 +1,26 @@
+use qap_local_search::features::{features_to_markdown, inspect, save_features};
+
+use std::env;
+use std::path::Path;
+
+/// The name of the folder where ths csv files should be saved
+const RESULTS_FOLDER: &str = "results";
+/// Folder with instances. This folder should be inside ./data
+const DATA_FOLDER: &str = "qapdatsol";
+
+/// Prints a table of descriptive features of all instances in a folder
+/// (the data folder by default) and saves it in a csv file
+fn main() {
+    let default_folder = Path::new(".").join("data").join(DATA_FOLDER).to_string_lossy().to_string();
+    let folder = env::args().nth(1).unwrap_or(default_folder);
+    match inspect(&folder) {
+        Ok(features) => {
+            println!("{}", features_to_markdown(&features));
+            let path = Path::new(".").join(RESULTS_FOLDER).join("features.csv");
+            if let Err(err) = save_features(&features, &path.to_string_lossy()) {
+                eprintln!("Error: {}", err);
+            }
+        }
+        Err(err) => eprintln!("Error: {}", err),
+    }
+}
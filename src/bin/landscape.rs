@@ -0,0 +1,51 @@
+use qap_local_search::landscape::{analyse, save_landscape_metrics, LandscapeSettings};
+use qap_local_search::qap_problem::QapProblem;
+use qap_local_search::solution::Solution;
+
+use std::path::Path;
+
+/// Instance file names
+const INSTANCES: [&str; 8] = [
+    "nug15.dat",
+    "els19.dat",
+    "had20.dat",
+    "bur26c.dat",
+    "nug30.dat",
+    "tai60a.dat",
+    "wil100.dat",
+    "esc128.dat",
+];
+
+/// The name of the folder where ths csv files should be saved
+const RESULTS_FOLDER: &str = "results";
+/// Folder with instances. This folder should be inside ./data
+const DATA_FOLDER: &str = "qapdatsol";
+
+/// Computes fitness landscape metrics of the instances
+fn main() {
+    let settings = LandscapeSettings::default();
+    let mut metrics = vec![];
+    for instance_filename in INSTANCES {
+        let instance_path = Path::new(".")
+            .join("data")
+            .join(DATA_FOLDER)
+            .join(instance_filename)
+            .to_string_lossy()
+            .to_string();
+        println!("{}", instance_filename);
+        let sln_path = Path::new(&instance_path).with_extension("sln");
+        let optimum = Solution::read_sln(&sln_path.to_string_lossy()).ok();
+        match QapProblem::new(&instance_path) {
+            Ok(qap_problem) => {
+                let instance_metrics = analyse(instance_filename, &qap_problem, optimum.as_ref(), &settings);
+                println!("{:?}", instance_metrics);
+                metrics.push(instance_metrics);
+            }
+            Err(err) => eprintln!("Error: {}", err),
+        }
+    }
+    let path = Path::new(".").join(RESULTS_FOLDER).join("landscape.csv");
+    if let Err(err) = save_landscape_metrics(&metrics, &path.to_string_lossy()) {
+        eprintln!("Error: {}", err);
+    }
+}
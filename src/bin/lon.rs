@@ -0,0 +1,65 @@
+use qap_local_search::landscape::lon::{LocalOptimaNetwork, LonSettings};
+use qap_local_search::qap_problem::QapProblem;
+
+use std::fs;
+use std::path::Path;
+
+/// Instance file names
+const INSTANCES: [&str; 6] = [
+    "nug15.dat",
+    "els19.dat",
+    "had20.dat",
+    "nug30.dat",
+    "tai60a.dat",
+    "tai60b.dat",
+];
+
+/// The name of the folder where ths csv files should be saved
+const RESULTS_FOLDER: &str = "results";
+/// Folder with instances. This folder should be inside ./data
+const DATA_FOLDER: &str = "qapdatsol";
+/// The name of the subfolder of the results folder with the networks
+const LON_FOLDER: &str = "lon";
+
+/// Samples local optima networks of the instances
+fn main() {
+    let settings = LonSettings::default();
+    let lon_path = Path::new(".").join(RESULTS_FOLDER).join(LON_FOLDER);
+    if let Err(err) = fs::create_dir_all(&lon_path) {
+        eprintln!("Error: {}", err);
+        return;
+    }
+    for instance_filename in INSTANCES {
+        let instance_path = Path::new(".")
+            .join("data")
+            .join(DATA_FOLDER)
+            .join(instance_filename)
+            .to_string_lossy()
+            .to_string();
+        match QapProblem::new(&instance_path) {
+            Ok(qap_problem) => {
+                let network = LocalOptimaNetwork::sample(&qap_problem, &settings);
+                println!(
+                    "{}: {} local optima, {} edges, {} sinks",
+                    instance_filename,
+                    network.nodes.len(),
+                    network.edges.len(),
+                    network.sinks().len()
+                );
+                let path = |suffix: &str| {
+                    lon_path
+                        .join(format!("{}_{}", instance_filename, suffix))
+                        .to_string_lossy()
+                        .to_string()
+                };
+                if let Err(err) = network.save_csv(&path("nodes.csv"), &path("edges.csv")) {
+                    eprintln!("Error: {}", err);
+                }
+                if let Err(err) = network.save_graphml(&path("lon.graphml")) {
+                    eprintln!("Error: {}", err);
+                }
+            }
+            Err(err) => eprintln!("Error: {}", err),
+        }
+    }
+}
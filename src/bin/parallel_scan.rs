@@ -0,0 +1,134 @@
+use qap_local_search::parallel::available_threads;
+use qap_local_search::qap_problem::QapProblem;
+use qap_local_search::solution::Solution;
+use qap_local_search::solvers::steepest_ls_solver::SteepestLSSolver;
+use qap_local_search::solvers::tabu_search_solver::TSSolver;
+use qap_local_search::utils::{arange, permute_array};
+
+use csv::Writer;
+use std::error::Error;
+use std::path::Path;
+use std::time::Instant;
+
+/// Instance file names
+const INSTANCES: [&str; 3] = ["tai60a.dat", "wil100.dat", "esc128.dat"];
+
+/// The name of the folder where ths csv files should be saved
+const RESULTS_FOLDER: &str = "results";
+/// Folder with instances. This folder should be inside ./data
+const DATA_FOLDER: &str = "qapdatsol";
+/// How many times the experiments should be ran (for statistics)
+const NRUNS: usize = 5;
+
+/// The time of one thread count, with the evaluations of the runs
+struct ScanTiming {
+    instance: String,
+    solver: String,
+    n_threads: usize,
+    mean_time: f64,
+    speedup: f64,
+    evals: Vec<i32>,
+}
+
+/// Returns the thread counts to time: powers of two up to the
+/// available threads, and the available threads
+fn thread_counts() -> Vec<usize> {
+    let available = available_threads();
+    let mut counts: Vec<usize> = (0..).map(|p| 1 << p).take_while(|&t| t < available).collect();
+    counts.push(available);
+    counts
+}
+
+fn random_solution(n: usize) -> Solution {
+    let mut solution_array = vec![0; n];
+    arange(&mut solution_array, 0, 1);
+    permute_array(&mut rand::thread_rng(), &mut solution_array);
+    Solution::new(solution_array)
+}
+
+/// Runs the solver from every initial solution and returns
+/// the mean time (ms) and the final evaluations
+fn time_runs<F: FnMut(Solution) -> Solution>(initial_solutions: &[Solution], mut solve: F) -> (f64, Vec<i32>) {
+    let start = Instant::now();
+    let evals = initial_solutions.iter().map(|s| solve(s.clone()).get_eval()).collect();
+    (start.elapsed().as_secs_f64() * 1000.0 / initial_solutions.len() as f64, evals)
+}
+
+fn save_timings(timings: &[ScanTiming], path: &str) -> Result<(), Box<dyn Error>> {
+    let mut wtr = Writer::from_path(path)?;
+    wtr.write_record(["instance", "solver", "n_threads", "mean_time", "speedup", "mean_eval"])?;
+    for timing in timings {
+        let mean_eval = timing.evals.iter().map(|&e| e as f64).sum::<f64>() / timing.evals.len() as f64;
+        wtr.write_record(&[
+            timing.instance.clone(),
+            timing.solver.clone(),
+            timing.n_threads.to_string(),
+            format!("{:.3}", timing.mean_time),
+            format!("{:.3}", timing.speedup),
+            format!("{:.1}", mean_eval),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Times steepest descent and tabu search with neighbourhood scans
+/// split across 1 to all available threads. Steepest descent from
+/// the same initial solutions must give the same local optima.
+fn main() {
+    let mut timings: Vec<ScanTiming> = vec![];
+    for instance_filename in INSTANCES {
+        let instance_path = Path::new(".")
+            .join("data")
+            .join(DATA_FOLDER)
+            .join(instance_filename)
+            .to_string_lossy()
+            .to_string();
+        let mut qap_problem = match QapProblem::new(&instance_path) {
+            Ok(qap_problem) => qap_problem,
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                continue;
+            }
+        };
+        let initial_solutions: Vec<Solution> = (0..NRUNS).map(|_| random_solution(qap_problem.get_n())).collect();
+        for solver_name in ["steepest", "tabu"] {
+            let mut baseline: Option<(f64, Vec<i32>)> = None;
+            for n_threads in thread_counts() {
+                let (mean_time, evals) = if solver_name == "steepest" {
+                    let mut solver = SteepestLSSolver::new(&qap_problem);
+                    solver.set_n_threads(n_threads);
+                    time_runs(&initial_solutions, |s| solver.solve_steepest(s))
+                } else {
+                    let mut solver = TSSolver::new(&mut qap_problem, 20, 0.7, 10, 100);
+                    solver.set_n_threads(n_threads);
+                    time_runs(&initial_solutions, |s| solver.solve_tabu_search(s))
+                };
+                let (base_time, base_evals) = baseline.get_or_insert_with(|| (mean_time, evals.clone()));
+                if solver_name == "steepest" && *base_evals != evals {
+                    eprintln!("Error: {} threads changed the local optima of {}", n_threads, instance_filename);
+                }
+                println!(
+                    "{} {} {} threads: {:.2} ms, speedup {:.2}",
+                    instance_filename,
+                    solver_name,
+                    n_threads,
+                    mean_time,
+                    *base_time / mean_time
+                );
+                timings.push(ScanTiming {
+                    instance: instance_filename.to_string(),
+                    solver: solver_name.to_string(),
+                    n_threads,
+                    mean_time,
+                    speedup: *base_time / mean_time,
+                    evals,
+                });
+            }
+        }
+    }
+    let path = Path::new(".").join(RESULTS_FOLDER).join("parallel_scan.csv");
+    if let Err(err) = save_timings(&timings, &path.to_string_lossy()) {
+        eprintln!("Error: {}", err);
+    }
+}
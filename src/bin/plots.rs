@@ -0,0 +1,38 @@
+use qap_local_search::report::{instance_charts, read_results_folder, save_svg};
+
+use std::fs;
+use std::path::Path;
+
+/// The name of the folder with the csv files of the experiments
+const RESULTS_FOLDER: &str = "results";
+/// Folder with instances. This folder should be inside ./data
+const DATA_FOLDER: &str = "qapdatsol";
+/// The name of the folder where the plots should be saved
+const PLOTS_FOLDER: &str = "saved_plots";
+
+/// Renders SVG charts of all experiment results, replacing the notebooks
+fn main() {
+    let data_path = Path::new(".").join("data").join(DATA_FOLDER).to_string_lossy().to_string();
+    let plots_path = Path::new(".").join("plotting").join(PLOTS_FOLDER);
+    if let Err(err) = fs::create_dir_all(&plots_path) {
+        eprintln!("Error: {}", err);
+        return;
+    }
+    match read_results_folder(RESULTS_FOLDER, &data_path) {
+        Ok(instances) => {
+            for instance in &instances {
+                println!("{}", instance.instance);
+                for (name, svg) in instance_charts(instance) {
+                    let path = plots_path
+                        .join(format!("{}_{}.svg", instance.instance, name))
+                        .to_string_lossy()
+                        .to_string();
+                    if let Err(err) = save_svg(&svg, &path) {
+                        eprintln!("Error: {}", err);
+                    }
+                }
+            }
+        }
+        Err(err) => eprintln!("Error: {}", err),
+    }
+}
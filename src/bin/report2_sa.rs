@@ -1,7 +1,10 @@
+use qap_local_search::checkpoint::Checkpoint;
 use qap_local_search::experiment::Experiment;
 use qap_local_search::qap_problem::QapProblem;
+use qap_local_search::report::TRACES_FOLDER;
 
 use qap_local_search::solvers::simulated_annealing_solver::SASolver;
+use qap_local_search::solvers::registry::Parameters;
 use qap_local_search::utils::parse_json;
 
 use std::path::Path;
@@ -20,13 +23,31 @@ const INSTANCES: [&str; 8] = [
 
 /// The name of the folder where ths csv files should be saved
 const RESULTS_FOLDER: &str = "results";
+/// Folder (inside RESULTS_FOLDER) with completed runs and solver states
+const CHECKPOINT_FOLDER: &str = "checkpoints";
 /// Folder with instances. This folder should be inside ./data
 const DATA_FOLDER: &str = "qapdatsol";
 /// How many times the experiments should be ran (for statistics)
 const NRUNS: usize = 10;
 
-/// Runs experiments across various solvers (algorithms)
+/// Runs experiments across various solvers (algorithms).
+/// With --resume, runs completed before an interruption are not repeated.
+/// With --config <path>, the parameters are read from the given json file
+/// (e.g. configs/sa_tuned.json) instead of configs/sa.json.
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let resume = args.iter().any(|arg| arg == "--resume");
+    let config_path = args
+        .iter()
+        .position(|arg| arg == "--config")
+        .and_then(|i| args.get(i + 1).cloned())
+        .unwrap_or_else(|| Path::new(".").join("configs").join("sa.json").to_string_lossy().to_string());
+    let config = parse_json(&config_path);
+    let checkpoint_folder = Path::new(".")
+        .join(RESULTS_FOLDER)
+        .join(CHECKPOINT_FOLDER)
+        .to_string_lossy()
+        .to_string();
     for instance_filename in INSTANCES {
         let instance_path = Path::new(".")
             .join("data")
@@ -35,14 +56,6 @@ fn main() {
             .to_string_lossy()
             .to_string();
         println!("{}", instance_filename);
-
-        // Read json config
-        let config_path = Path::new(".")
-        .join("configs")
-        .join("sa.json").to_string_lossy()
-        .to_string();
-
-        let config = parse_json(&config_path);
         match QapProblem::new(&instance_path) {
             Ok(mut qap_problem) => {
                 println!("Simulated Annealing Solver");
@@ -55,6 +68,15 @@ fn main() {
                 let mut sa_solver: SASolver<'_> = SASolver::new(&mut qap_problem, temp_mul, iter_mul, l_div);
                 sa_solver.compute_initial_temperature();
                 let mut experiment = Experiment::new(&mut sa_solver, NRUNS);
+                let parameters: Parameters = [("temp_mul", temp_mul as f64), ("iter_mul", iter_mul as f64), ("l_div", l_div as f64)]
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), *value))
+                    .collect();
+                match Checkpoint::new(&checkpoint_folder, instance_filename, "sa", &parameters, resume) {
+                    Ok(checkpoint) => experiment.set_checkpoint(checkpoint),
+                    Err(err) => eprintln!("Checkpointing disabled: {}", err),
+                }
+                experiment.set_record_traces(true);
                 experiment.run();
                 let path = Path::new(".")
                     .join(RESULTS_FOLDER)
@@ -62,6 +84,13 @@ fn main() {
                     .to_string_lossy()
                     .to_string();
                 let _ = experiment.save_results(&path);
+                let traces_folder = Path::new(".").join(RESULTS_FOLDER).join(TRACES_FOLDER);
+                let _ = std::fs::create_dir_all(&traces_folder);
+                let traces_path = traces_folder
+                    .join(instance_filename.to_owned() + "_sa.csv")
+                    .to_string_lossy()
+                    .to_string();
+                let _ = experiment.save_traces(&traces_path);
             }
             Err(err) => eprintln!("Error: {}", err),
         }
@@ -1,8 +1,11 @@
+use qap_local_search::checkpoint::Checkpoint;
 use qap_local_search::experiment::Experiment;
 use qap_local_search::qap_problem::QapProblem;
+use qap_local_search::report::TRACES_FOLDER;
 
 use qap_local_search::solvers::solver::Solver;
 use qap_local_search::solvers::tabu_search_solver::TSSolver;
+use qap_local_search::solvers::registry::Parameters;
 use qap_local_search::utils::parse_json;
 
 use std::path::Path;
@@ -21,13 +24,31 @@ const INSTANCES: [&str; 8] = [
 
 /// The name of the folder where ths csv files should be saved
 const RESULTS_FOLDER: &str = "results";
+/// Folder (inside RESULTS_FOLDER) with completed runs and solver states
+const CHECKPOINT_FOLDER: &str = "checkpoints";
 /// Folder with instances. This folder should be inside ./data
 const DATA_FOLDER: &str = "qapdatsol";
 /// How many times the experiments should be ran (for statistics)
 const NRUNS: usize = 20;
 
-/// Runs experiments across various solvers (algorithms)
+/// Runs experiments across various solvers (algorithms).
+/// With --resume, runs completed before an interruption are not repeated.
+/// With --config <path>, the parameters are read from the given json file
+/// (e.g. configs/tabu_tuned.json) instead of configs/tabu.json.
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let resume = args.iter().any(|arg| arg == "--resume");
+    let config_path = args
+        .iter()
+        .position(|arg| arg == "--config")
+        .and_then(|i| args.get(i + 1).cloned())
+        .unwrap_or_else(|| Path::new(".").join("configs").join("tabu.json").to_string_lossy().to_string());
+    let config = parse_json(&config_path);
+    let checkpoint_folder = Path::new(".")
+        .join(RESULTS_FOLDER)
+        .join(CHECKPOINT_FOLDER)
+        .to_string_lossy()
+        .to_string();
     for instance_filename in INSTANCES {
         let instance_path = Path::new(".")
             .join("data")
@@ -36,14 +57,6 @@ fn main() {
             .to_string_lossy()
             .to_string();
         println!("{}", instance_filename);
-
-        // Read json config
-        let config_path = Path::new(".")
-        .join("configs")
-        .join("tabu.json").to_string_lossy()
-        .to_string();
-;
-        let config = parse_json(&config_path);
         match QapProblem::new(&instance_path) {
             Ok(mut qap_problem) => {
                 println!("Tabu Search Solver");
@@ -51,11 +64,29 @@ fn main() {
                 let tenure = params.get("tenure").unwrap().as_i64().unwrap() as i32;
                 let k = params.get("k").unwrap().as_u64().unwrap() as usize;
                 let lack_impr_iter = params.get("lack_impr_iter").unwrap().as_i64().unwrap() as i32;
-                println!("Params: tenure = {}, k= {}, lack iter = {}", tenure, k, lack_impr_iter);
-                let mut ts_solver: TSSolver<'_> = TSSolver::new(&mut qap_problem, tenure, 0.7, k, lack_impr_iter);
+                let cn_ratio = params.get("cn_ratio").and_then(|v| v.as_f64()).unwrap_or(0.7) as f32;
+                println!(
+                    "Params: tenure = {}, k= {}, lack iter = {}, cn ratio = {}",
+                    tenure, k, lack_impr_iter, cn_ratio
+                );
+                let mut ts_solver: TSSolver<'_> = TSSolver::new(&mut qap_problem, tenure, cn_ratio, k, lack_impr_iter);
                 // let sol = ts_solver.solve();
                 // println!("Eval {}", sol.get_eval());
                 let mut experiment = Experiment::new(&mut ts_solver, NRUNS);
+                let parameters: Parameters = [
+                    ("tenure", tenure as f64),
+                    ("k", k as f64),
+                    ("lack_impr_iter", lack_impr_iter as f64),
+                    ("cn_ratio", cn_ratio as f64),
+                ]
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), *value))
+                    .collect();
+                match Checkpoint::new(&checkpoint_folder, instance_filename, "tabu", &parameters, resume) {
+                    Ok(checkpoint) => experiment.set_checkpoint(checkpoint),
+                    Err(err) => eprintln!("Checkpointing disabled: {}", err),
+                }
+                experiment.set_record_traces(true);
                 experiment.run();
                 let path = Path::new(".")
                     .join(RESULTS_FOLDER)
@@ -63,6 +94,13 @@ fn main() {
                     .to_string_lossy()
                     .to_string();
                 let _ = experiment.save_results(&path);
+                let traces_folder = Path::new(".").join(RESULTS_FOLDER).join(TRACES_FOLDER);
+                let _ = std::fs::create_dir_all(&traces_folder);
+                let traces_path = traces_folder
+                    .join(instance_filename.to_owned() + "_tabu.csv")
+                    .to_string_lossy()
+                    .to_string();
+                let _ = experiment.save_traces(&traces_path);
             }
             Err(err) => eprintln!("Error: {}", err),
         }
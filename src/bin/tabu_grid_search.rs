@@ -1,11 +1,6 @@
-use qap_local_search::experiment::Experiment;
-use qap_local_search::qap_problem::QapProblem;
+use qap_local_search::tuning::parameter_search::ParameterSearch;
+use qap_local_search::tuning::parameters::ParameterDomain;
 
-use qap_local_search::solvers::tabu_search_solver::TSSolver;
-
-use core::cmp::max;
-use core::cmp::min;
-use csv::Writer;
 use std::path::Path;
 
 /// Instance file names
@@ -28,12 +23,24 @@ const DATA_FOLDER: &str = "qapdatsol";
 const NRUNS: usize = 10;
 
 // Grid Search parameters
-const TENURES: [f32; 3] = [0.1, 0.25, 0.35];
-const KS: [f32; 3] = [0.7, 2.5, 3.5];
-const LITS: [i32; 3] = [50, 100, 300];
+/// Tenures as fractions of the instance size
+const TENURES: [f64; 3] = [0.1, 0.25, 0.35];
+/// Candidate list sizes as multiples of the tenure
+const KS: [f64; 3] = [0.7, 2.5, 3.5];
+const LITS: [f64; 3] = [50.0, 100.0, 300.0];
 
-/// Runs experiments across various solvers (algorithms)
+/// Runs a grid search over the tabu search parameters
 fn main() {
+    let search = ParameterSearch::grid(
+        "tabu",
+        vec![
+            (ParameterDomain::fraction_of_n("tenure", 0.0, 1.0), TENURES.to_vec()),
+            (ParameterDomain::multiple_of("k", "tenure", 0.0, 10.0), KS.to_vec()),
+            (ParameterDomain::integer("lack_impr_iter", 1.0, 1000.0), LITS.to_vec()),
+            (ParameterDomain::real("cn_ratio", 1.0, 1.0), vec![1.0]),
+        ],
+        NRUNS,
+    );
     for instance_filename in INSTANCES {
         let instance_path = Path::new(".")
             .join("data")
@@ -41,58 +48,14 @@ fn main() {
             .join(instance_filename)
             .to_string_lossy()
             .to_string();
-        println!("{}", instance_filename);
-        match QapProblem::new(&instance_path) {
-            Ok(mut qap_problem) => {
-                // Set path to save the result
-                let path = Path::new(".")
-                    .join(RESULTS_FOLDER)
-                    .join(instance_filename.to_owned() + "_tabu_gs.csv")
-                    .to_string_lossy()
-                    .to_string();
-                // Initialize a CSV Writer
-                let mut wtr = Writer::from_path(&path).unwrap();
-                // Write column names
-                let _ = wtr.write_record(&[
-                    "tenure",
-                    "k",
-                    "lack_impr_iter",
-                    "eval_mean",
-                    "eval_std",
-                    "time_mean",
-                    "time_std",
-                ]);
-                let N = qap_problem.get_n();
-
-                for t in TENURES {
-                    for k in KS {
-                        for lit in LITS {
-                            // Calculate the parameters as integers
-                            let int_t = (N as f32 * t) as i32;
-                            let int_k = max((int_t as f32 * k ) as usize, 1);
-                            /// let lit = min(int_t - 2, lit);
-                            println!("Tabu Search Solver t = {}, k = {}, lit = {}", t, k, lit);
-                            println!("int_t = {}, int_k = {}", int_t, int_k);
-                            let mut ts_solver: TSSolver<'_> =
-                                TSSolver::new(&mut qap_problem, int_t, 1.0, int_k, lit);
-                            let mut experiment = Experiment::new(&mut ts_solver, NRUNS);
-                            experiment.run();
-                            // Collect the experiment statistics
-                            let (eval_mean, eval_std) = experiment.get_final_evaluation_mean_std();
-                            let (time_mean, time_std) = experiment.get_elapsed_time_mean_std();
-                            // Write parameters and results to csv
-                            let _ = wtr.write_record(&[
-                                int_t.to_string(), int_k.to_string(), lit.to_string(),
-                                eval_mean.to_string(), eval_std.to_string(),
-                                time_mean.to_string(), time_std.to_string(),
-                            ]);
-                        }
-                    }
-                }
-                let _ = wtr.flush();
-                break;
-            }
-            Err(err) => eprintln!("Error: {}", err),
+        // Set path to save the result
+        let path = Path::new(".")
+            .join(RESULTS_FOLDER)
+            .join(instance_filename.to_owned() + "_tabu_gs.csv")
+            .to_string_lossy()
+            .to_string();
+        if let Err(err) = search.run(&[instance_path], &path) {
+            eprintln!("Error: {}", err);
         }
     }
 }
@@ -0,0 +1,67 @@
+use qap_local_search::experiment::Experiment;
+use qap_local_search::qap_problem::QapProblem;
+use qap_local_search::run_length::{save_ecdf_tables, save_fit_summaries, Target};
+use qap_local_search::solution::Solution;
+use qap_local_search::solvers::registry::{build_solver, default_parameters};
+
+use std::path::Path;
+
+/// Instance file names
+const INSTANCES: [&str; 4] = ["nug15.dat", "had20.dat", "els19.dat", "nug30.dat"];
+
+/// Solvers to compare
+const SOLVERS: [&str; 2] = ["tabu", "sa"];
+
+/// Target gaps (%) to the optimum
+const TARGET_GAPS: [f64; 4] = [0.0, 0.5, 1.0, 2.0];
+
+/// The name of the folder where ths csv files should be saved
+const RESULTS_FOLDER: &str = "results";
+/// Folder with instances. This folder should be inside ./data
+const DATA_FOLDER: &str = "qapdatsol";
+/// How many times the experiments should be ran (for statistics)
+const NRUNS: usize = 50;
+
+/// Records traces of the solvers and exports time-to-target plots data:
+/// the empirical distributions and the fitted shifted exponentials
+fn main() {
+    let targets: Vec<Target> = TARGET_GAPS.iter().map(|&gap| Target::Gap(gap)).collect();
+    let mut distributions = vec![];
+    for instance_filename in INSTANCES {
+        let instance_path = Path::new(".")
+            .join("data")
+            .join(DATA_FOLDER)
+            .join(instance_filename)
+            .to_string_lossy()
+            .to_string();
+        println!("{}", instance_filename);
+        let sln_path = Path::new(&instance_path).with_extension("sln");
+        let optimum = Solution::read_sln(&sln_path.to_string_lossy()).ok().map(|s| s.get_eval());
+        match QapProblem::new(&instance_path) {
+            Ok(mut qap_problem) => {
+                for solver_name in SOLVERS {
+                    let parameters = default_parameters(solver_name);
+                    let mut solver = build_solver(solver_name, &mut qap_problem, &parameters).unwrap();
+                    let mut experiment = Experiment::new(&mut *solver, NRUNS);
+                    experiment.set_record_traces(true);
+                    experiment.run();
+                    distributions.extend(experiment.get_run_length_distributions(
+                        instance_filename,
+                        solver_name,
+                        &targets,
+                        optimum,
+                    ));
+                }
+            }
+            Err(err) => eprintln!("Error: {}", err),
+        }
+    }
+    let ecdf_path = Path::new(".").join(RESULTS_FOLDER).join("ttt_ecdf.csv");
+    if let Err(err) = save_ecdf_tables(&distributions, &ecdf_path.to_string_lossy()) {
+        eprintln!("Error: {}", err);
+    }
+    let fits_path = Path::new(".").join(RESULTS_FOLDER).join("ttt_fits.csv");
+    if let Err(err) = save_fit_summaries(&distributions, &fits_path.to_string_lossy()) {
+        eprintln!("Error: {}", err);
+    }
+}
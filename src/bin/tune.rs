@@ -0,0 +1,62 @@
+use qap_local_search::tuning::parameters::{default_domains, save_configs};
+use qap_local_search::tuning::racing::{tune_by_class, RaceSettings};
+
+use std::path::Path;
+
+/// Instance file names used for tuning
+const INSTANCES: [&str; 8] = [
+    "nug30.dat",
+    "tai60a.dat",
+    "wil100.dat",
+    "bur26c.dat",
+    "els19.dat",
+    "esc128.dat",
+    "had20.dat",
+    "nug15.dat",
+];
+
+/// Solvers to tune
+const SOLVERS: [&str; 2] = ["tabu", "sa"];
+
+/// The name of the folder where the tuned configs should be saved
+const CONFIGS_FOLDER: &str = "configs";
+/// Folder with instances. This folder should be inside ./data
+const DATA_FOLDER: &str = "qapdatsol";
+/// The total number of solver runs per instance class
+const BUDGET: usize = 300;
+
+/// Tunes solver parameters by racing and saves the configs
+/// in the format read by the report binaries
+fn main() {
+    let instance_paths: Vec<String> = INSTANCES
+        .iter()
+        .map(|instance_filename| {
+            Path::new(".")
+                .join("data")
+                .join(DATA_FOLDER)
+                .join(instance_filename)
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect();
+    let settings = RaceSettings {
+        budget: BUDGET,
+        ..RaceSettings::default()
+    };
+    for solver in SOLVERS {
+        let domains = default_domains(solver);
+        match tune_by_class(solver, &domains, &instance_paths, &settings) {
+            Ok(configs) => {
+                let path = Path::new(".")
+                    .join(CONFIGS_FOLDER)
+                    .join(solver.to_owned() + "_tuned.json")
+                    .to_string_lossy()
+                    .to_string();
+                if let Err(err) = save_configs(&configs, &domains, &path) {
+                    eprintln!("Error: {}", err);
+                }
+            }
+            Err(err) => eprintln!("Error: {}", err),
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use std::fmt;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+use crate::solution::Solution;
+use crate::tuning::parameters::instance_class;
+
+/// QAPLIB category of an instance family, following Taillard's classification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    /// Instances from real-life applications, or generated to resemble them
+    RealLife,
+    /// Unstructured instances with uniformly random matrices
+    RandomUniform,
+    /// Distances are Manhattan distances between the cells of a grid
+    GridBased,
+    /// Generated instances with a known optimal solution
+    KnownOptimum,
+}
+
+impl Category {
+    /// All categories
+    pub const ALL: [Category; 4] = [
+        Category::RealLife,
+        Category::RandomUniform,
+        Category::GridBased,
+        Category::KnownOptimum,
+    ];
+
+    /// Returns the short name of the category, as used in selections
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::RealLife => "real",
+            Category::RandomUniform => "random",
+            Category::GridBased => "grid",
+            Category::KnownOptimum => "generated",
+        }
+    }
+
+    /// Returns the category of a short name
+    pub fn from_name(name: &str) -> Option<Category> {
+        Category::ALL.into_iter().find(|category| category.name() == name)
+    }
+
+    /// Returns the category of an instance family and variant, None
+    /// for families outside QAPLIB
+    pub fn of(family: &str, variant: &str) -> Option<Category> {
+        match family {
+            "tai" if variant == "a" => Some(Category::RandomUniform),
+            "tai" => Some(Category::RealLife),
+            "rou" => Some(Category::RandomUniform),
+            "nug" | "sko" | "wil" | "tho" | "had" | "scr" => Some(Category::GridBased),
+            "bur" | "chr" | "els" | "esc" | "kra" | "ste" => Some(Category::RealLife),
+            "lipa" => Some(Category::KnownOptimum),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Returns the file name without the extension
+fn stem(filename: &str) -> String {
+    Path::new(filename)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Returns the family of an instance: the letters before the size,
+/// e.g. "tai60a.dat" -> "tai"
+pub fn instance_family(filename: &str) -> String {
+    stem(filename).chars().take_while(|c| c.is_ascii_alphabetic()).collect()
+}
+
+/// Returns the variant of an instance: the letters after the size,
+/// e.g. "tai60a.dat" -> "a", empty if there is none
+pub fn instance_variant(filename: &str) -> String {
+    stem(filename)
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect()
+}
+
+/// Returns true if the text matches the glob pattern,
+/// where `*` matches any sequence and `?` any single character
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+    // Position in the pattern after the last `*` and the text position it matched up to
+    let mut star: Option<(usize, usize)> = None;
+    let (mut p, mut t) = (0, 0);
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p + 1, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // Let the last `*` match one more character
+            p = star_p;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// An instance of the catalogue
+#[derive(Debug, Clone)]
+pub struct CatalogueEntry {
+    /// File name of the instance, e.g. "tai60a.dat"
+    pub name: String,
+    pub family: String,
+    /// Class used to share tuned parameters, see tuning::parameters::instance_class
+    pub class: String,
+    pub category: Option<Category>,
+    pub n: usize,
+    /// Path of the .dat file
+    pub path: String,
+    /// Path of the .sln file, if there is one
+    pub sln_path: Option<String>,
+    /// Evaluation of the optimal (best known) solution from the .sln file
+    pub optimum: Option<i32>,
+}
+
+impl CatalogueEntry {
+    /// Returns the optimal (best known) solution from the .sln file
+    pub fn read_solution(&self) -> Option<Solution> {
+        self.sln_path.as_ref().and_then(|path| Solution::read_sln(path).ok())
+    }
+}
+
+/// Reads the instance size, the first number of a .dat file
+fn read_size(path: &Path) -> Result<usize, Error> {
+    let content = fs::read_to_string(path)?;
+    content
+        .split_whitespace()
+        .next()
+        .and_then(|token| token.parse().ok())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("{}: invalid instance size", path.display())))
+}
+
+/// All instances of a data folder, sorted by family and size
+pub struct Catalogue {
+    entries: Vec<CatalogueEntry>,
+}
+
+impl Catalogue {
+    /// Scans a data folder for .dat files and pairs them with the .sln
+    /// files of the same name. Files which can not be read are skipped.
+    pub fn scan(folder: &str) -> Result<Catalogue, Error> {
+        let mut entries = vec![];
+        for entry in fs::read_dir(folder)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|e| e != "dat") {
+                continue;
+            }
+            let name = path.file_name().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+            let n = match read_size(&path) {
+                Ok(n) => n,
+                Err(err) => {
+                    eprintln!("Skipping {}: {}", name, err);
+                    continue;
+                }
+            };
+            let sln_path = path.with_extension("sln");
+            let sln_path = sln_path.exists().then(|| sln_path.to_string_lossy().to_string());
+            let optimum = sln_path
+                .as_ref()
+                .and_then(|sln_path| Solution::read_sln(sln_path).ok())
+                .map(|solution| solution.get_eval());
+            let family = instance_family(&name);
+            entries.push(CatalogueEntry {
+                category: Category::of(&family, &instance_variant(&name)),
+                class: instance_class(&name),
+                family,
+                n,
+                path: path.to_string_lossy().to_string(),
+                sln_path,
+                optimum,
+                name,
+            });
+        }
+        entries.sort_by(|a, b| (&a.family, a.n, &a.name).cmp(&(&b.family, b.n, &b.name)));
+        Ok(Catalogue { entries })
+    }
+
+    /// Returns all instances
+    pub fn entries(&self) -> &[CatalogueEntry] {
+        &self.entries
+    }
+
+    /// Returns the instance with the given file name
+    pub fn get(&self, name: &str) -> Option<&CatalogueEntry> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    /// Returns the instances matching the selection
+    pub fn select(&self, selection: &Selection) -> Vec<&CatalogueEntry> {
+        self.entries.iter().filter(|entry| selection.matches(entry)).collect()
+    }
+}
+
+/// A set of instances. An instance is selected if its name (with or
+/// without the extension) matches any of the patterns and it satisfies
+/// all other conditions. Empty lists do not restrict the selection.
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    /// Glob patterns of the names, e.g. "tai*a"
+    pub patterns: Vec<String>,
+    pub min_n: Option<usize>,
+    pub max_n: Option<usize>,
+    pub classes: Vec<String>,
+    pub categories: Vec<Category>,
+    /// Only instances with a .sln file
+    pub with_solution: bool,
+}
+
+impl Selection {
+    /// Parses a selection from terms separated by whitespace or commas:
+    /// name patterns, size bounds ("n<=60", "n>=20", "n=30"),
+    /// "class=tai-a", "category=grid" and "solved" for instances with a
+    /// .sln file, e.g. "tai*a n<=60"
+    pub fn parse(text: &str) -> Result<Selection, String> {
+        let mut selection = Selection::default();
+        for term in text.split(|c: char| c.is_whitespace() || c == ',').filter(|t| !t.is_empty()) {
+            let parse_n = |value: &str| value.parse::<usize>().map_err(|_| format!("invalid size in {}", term));
+            if let Some(value) = term.strip_prefix("n<=") {
+                selection.max_n = Some(parse_n(value)?);
+            } else if let Some(value) = term.strip_prefix("n>=") {
+                selection.min_n = Some(parse_n(value)?);
+            } else if let Some(value) = term.strip_prefix("n=") {
+                selection.min_n = Some(parse_n(value)?);
+                selection.max_n = selection.min_n;
+            } else if let Some(value) = term.strip_prefix("class=") {
+                selection.classes.push(value.to_string());
+            } else if let Some(value) = term.strip_prefix("category=") {
+                let category = Category::from_name(value).ok_or_else(|| format!("unknown category {}", value))?;
+                selection.categories.push(category);
+            } else if term == "solved" {
+                selection.with_solution = true;
+            } else {
+                selection.patterns.push(term.to_string());
+            }
+        }
+        Ok(selection)
+    }
+
+    /// Returns true if the instance belongs to the selection
+    pub fn matches(&self, entry: &CatalogueEntry) -> bool {
+        let stem = stem(&entry.name);
+        (self.patterns.is_empty()
+            || self
+                .patterns
+                .iter()
+                .any(|pattern| glob_match(pattern, &entry.name) || glob_match(pattern, &stem)))
+            && self.min_n.is_none_or(|min_n| entry.n >= min_n)
+            && self.max_n.is_none_or(|max_n| entry.n <= max_n)
+            && (self.classes.is_empty() || self.classes.contains(&entry.class))
+            && (self.categories.is_empty() || entry.category.is_some_and(|c| self.categories.contains(&c)))
+            && (!self.with_solution || entry.sln_path.is_some())
+    }
+}
@@ -0,0 +1,280 @@
+use csv::{ReaderBuilder, WriterBuilder};
+use serde_json::{json, Value};
+use std::cell::Cell as StdCell;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::path::Path;
+use std::time::Instant;
+
+use crate::solution::Solution;
+use crate::solvers::registry::Parameters;
+
+/// A cell of an experiment campaign: one run of a solver configuration
+/// on an instance. Solvers draw from the thread rng, so the seed is the
+/// replicate index of the run rather than the state of its random stream.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cell {
+    pub instance: String,
+    pub solver: String,
+    /// The solver configuration (parameters as json)
+    pub config: String,
+    pub seed: usize,
+}
+
+/// Manifest of the completed cells of a campaign, stored in a csv file
+/// which is appended as soon as a cell completes
+pub struct Manifest {
+    path: String,
+    completed: HashSet<Cell>,
+}
+
+/// Column names of the manifest
+const MANIFEST_HEADER: [&str; 4] = ["instance", "solver", "config", "seed"];
+
+impl Manifest {
+    /// Reads the manifest or creates an empty one if the file does not exist
+    pub fn open(path: &str) -> Result<Manifest, Box<dyn Error>> {
+        let mut completed = HashSet::new();
+        if Path::new(path).exists() {
+            let mut rdr = ReaderBuilder::new().from_path(path)?;
+            for record in rdr.records() {
+                let record = record?;
+                completed.insert(Cell {
+                    instance: record[0].to_string(),
+                    solver: record[1].to_string(),
+                    config: record[2].to_string(),
+                    seed: record[3].parse()?,
+                });
+            }
+        }
+        let mut manifest = Manifest {
+            path: path.to_string(),
+            completed,
+        };
+        if !Path::new(path).exists() {
+            manifest.rewrite()?;
+        }
+        Ok(manifest)
+    }
+
+    /// Whether the cell has been completed
+    pub fn is_completed(&self, cell: &Cell) -> bool {
+        self.completed.contains(cell)
+    }
+
+    /// Records a completed cell and appends it to the file
+    pub fn mark_completed(&mut self, cell: Cell) -> Result<(), Box<dyn Error>> {
+        let file = OpenOptions::new().append(true).open(&self.path)?;
+        let mut wtr = WriterBuilder::new().has_headers(false).from_writer(file);
+        wtr.write_record([&cell.instance, &cell.solver, &cell.config, &cell.seed.to_string()])?;
+        wtr.flush()?;
+        self.completed.insert(cell);
+        Ok(())
+    }
+
+    /// Removes all cells of a solver configuration on an instance
+    pub fn forget(&mut self, instance: &str, solver: &str, config: &str) -> Result<(), Box<dyn Error>> {
+        self.completed
+            .retain(|cell| !(cell.instance == instance && cell.solver == solver && cell.config == config));
+        self.rewrite()
+    }
+
+    /// Writes all completed cells to the file
+    fn rewrite(&mut self) -> Result<(), Box<dyn Error>> {
+        let mut wtr = WriterBuilder::new().from_path(&self.path)?;
+        wtr.write_record(MANIFEST_HEADER)?;
+        for cell in &self.completed {
+            wtr.write_record([&cell.instance, &cell.solver, &cell.config, &cell.seed.to_string()])?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+/// Periodically saves the state of a running solver to a json file,
+/// together with the time elapsed in the run
+pub struct StateSnapshot {
+    path: String,
+    /// How many iterations pass between snapshots
+    interval: i32,
+    /// The iteration of the last snapshot
+    last_saved: StdCell<i32>,
+    started: Instant,
+    /// Time (ms) elapsed in the run before it was resumed
+    elapsed_offset: u128,
+}
+
+impl StateSnapshot {
+    /// Constructor
+    pub fn new(path: &str, interval: i32, elapsed_offset: u128) -> StateSnapshot {
+        StateSnapshot {
+            path: path.to_string(),
+            interval: interval.max(1),
+            last_saved: StdCell::new(0),
+            started: Instant::now(),
+            elapsed_offset,
+        }
+    }
+
+    /// Saves the state returned by `state` if at least `interval` iterations
+    /// passed since the last snapshot. The file is replaced atomically,
+    /// so an interruption while saving keeps the previous snapshot.
+    pub fn save_if_due<F: FnOnce() -> Value>(&self, iteration: i32, state: F) {
+        if iteration - self.last_saved.get() < self.interval {
+            return;
+        }
+        self.last_saved.set(iteration);
+        let elapsed = self.elapsed_offset + self.started.elapsed().as_millis();
+        let snapshot = json!({ "elapsed": elapsed as u64, "state": state() });
+        let tmp_path = self.path.clone() + ".tmp";
+        let result = File::create(&tmp_path)
+            .map_err(|e| e.to_string())
+            .and_then(|file| serde_json::to_writer(file, &snapshot).map_err(|e| e.to_string()))
+            .and_then(|_| fs::rename(&tmp_path, &self.path).map_err(|e| e.to_string()));
+        if let Err(err) = result {
+            eprintln!("Could not save the solver state to {}: {}", self.path, err);
+        }
+    }
+
+    /// Reads a snapshot, returns the elapsed time (ms) and the solver state
+    pub fn load(path: &str) -> Option<(u128, Value)> {
+        let file = File::open(path).ok()?;
+        let mut snapshot: Value = serde_json::from_reader(file).ok()?;
+        let elapsed = snapshot.get("elapsed")?.as_u64()? as u128;
+        Some((elapsed, snapshot.get_mut("state")?.take()))
+    }
+}
+
+/// Converts a solution to json
+pub fn solution_to_json(solution: &Solution) -> Value {
+    json!({ "solution": solution.solution_array, "eval": solution.get_eval() })
+}
+
+/// Reads a solution converted by solution_to_json
+pub fn solution_from_json(value: &Value) -> Option<Solution> {
+    let solution_array = value
+        .get("solution")?
+        .as_array()?
+        .iter()
+        .map(|v| v.as_u64().map(|v| v as usize))
+        .collect::<Option<Vec<usize>>>()?;
+    let mut solution = Solution::new(solution_array);
+    solution.set_eval(value.get("eval")?.as_i64()? as i32);
+    Some(solution)
+}
+
+/// Returns the FNV-1a hash of a text, stable across platforms and builds
+fn stable_hash(text: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Checkpointing of one experiment (a solver configuration on an instance)
+/// within a campaign folder. Completed runs are appended to a csv file and
+/// recorded in the campaign manifest, and the state of the running solver
+/// is saved periodically so an interrupted run can be continued.
+pub struct Checkpoint {
+    folder: String,
+    instance: String,
+    solver: String,
+    config: String,
+    manifest: Manifest,
+    /// How many solver iterations pass between state snapshots
+    snapshot_interval: i32,
+}
+
+impl Checkpoint {
+    /// Constructor. With `resume`, completed runs of the experiment found in
+    /// the folder are kept, otherwise the experiment starts from scratch.
+    pub fn new(
+        folder: &str,
+        instance: &str,
+        solver: &str,
+        parameters: &Parameters,
+        resume: bool,
+    ) -> Result<Checkpoint, Box<dyn Error>> {
+        fs::create_dir_all(folder)?;
+        let manifest_path = Path::new(folder).join("manifest.csv").to_string_lossy().to_string();
+        let mut checkpoint = Checkpoint {
+            folder: folder.to_string(),
+            instance: instance.to_string(),
+            solver: solver.to_string(),
+            config: serde_json::to_string(parameters)?,
+            manifest: Manifest::open(&manifest_path)?,
+            snapshot_interval: 1000,
+        };
+        if !resume {
+            checkpoint.manifest.forget(instance, solver, &checkpoint.config)?;
+            // Remove the results and state snapshots of the experiment
+            let prefix = checkpoint.file_prefix();
+            for entry in fs::read_dir(folder)? {
+                let path = entry?.path().to_string_lossy().to_string();
+                if path.starts_with(&prefix) {
+                    fs::remove_file(path)?;
+                }
+            }
+        }
+        Ok(checkpoint)
+    }
+
+    /// Sets how many solver iterations pass between state snapshots
+    pub fn set_snapshot_interval(&mut self, interval: i32) {
+        self.snapshot_interval = interval
+    }
+
+    /// Returns the cell of a run
+    pub fn cell(&self, run: usize) -> Cell {
+        Cell {
+            instance: self.instance.clone(),
+            solver: self.solver.clone(),
+            config: self.config.clone(),
+            seed: run,
+        }
+    }
+
+    /// Whether the run has been completed
+    pub fn is_completed(&self, run: usize) -> bool {
+        self.manifest.is_completed(&self.cell(run))
+    }
+
+    /// Records a completed run in the manifest and removes its snapshot
+    pub fn mark_completed(&mut self, run: usize) -> Result<(), Box<dyn Error>> {
+        self.manifest.mark_completed(self.cell(run))?;
+        let state_path = self.state_path(run);
+        if Path::new(&state_path).exists() {
+            fs::remove_file(state_path)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the common prefix of the files of this experiment
+    fn file_prefix(&self) -> String {
+        let name = format!(
+            "{}_{}_{:016x}",
+            self.instance,
+            self.solver,
+            stable_hash(&self.config)
+        );
+        Path::new(&self.folder).join(name).to_string_lossy().to_string()
+    }
+
+    /// Returns the path of the csv file with completed runs
+    pub fn results_path(&self) -> String {
+        self.file_prefix() + ".csv"
+    }
+
+    /// Returns the path of the state snapshot of a run
+    pub fn state_path(&self, run: usize) -> String {
+        format!("{}_run{}.state.json", self.file_prefix(), run)
+    }
+
+    /// Returns a snapshot writer for a run, starting at the given elapsed time
+    pub fn state_snapshot(&self, run: usize, elapsed_offset: u128) -> StateSnapshot {
+        StateSnapshot::new(&self.state_path(run), self.snapshot_interval, elapsed_offset)
+    }
+}
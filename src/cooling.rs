@@ -0,0 +1,319 @@
+use rand::Rng;
+
+/// Returns true if a move changing the evaluation by delta is accepted
+/// at the temperature by the Metropolis rule: improving moves always,
+/// worse moves with probability exp(-delta / T), none at zero temperature
+pub fn metropolis_accept<R: Rng + ?Sized>(delta: i32, temperature: f32, rng: &mut R) -> bool {
+    if delta < 0 {
+        return true;
+    }
+    let q = if temperature > 0.0 { (-delta as f32 / temperature).exp() } else { 0.0 };
+    q > rng.gen_range(0.0..1.0)
+}
+
+/// The state of the annealing at the end of a Markov chain,
+/// passed to a cooling schedule to compute the next temperature
+#[derive(Debug, Clone, Copy)]
+pub struct ChainStats {
+    /// The number of completed chains (temperature levels)
+    pub level: usize,
+    /// The temperature of the first chain
+    pub initial_temperature: f32,
+    /// The fraction of moves accepted in the last chain
+    pub acceptance_rate: f64,
+}
+
+/// A cooling schedule of simulated annealing, giving the
+/// temperature of the next Markov chain
+pub trait CoolingSchedule: Send {
+    /// Returns the name of the schedule
+    fn name(&self) -> &'static str;
+
+    /// Returns the temperature of the next chain
+    fn next_temperature(&mut self, temperature: f32, stats: &ChainStats) -> f32;
+
+    /// Resets the state of the schedule at the start of a run
+    fn reset(&mut self) {}
+}
+
+/// Geometric cooling: T <- alpha * T
+pub struct GeometricCooling {
+    pub alpha: f32,
+}
+
+impl CoolingSchedule for GeometricCooling {
+    fn name(&self) -> &'static str {
+        "geometric"
+    }
+    fn next_temperature(&mut self, temperature: f32, _stats: &ChainStats) -> f32 {
+        temperature * self.alpha
+    }
+}
+
+/// Linear cooling: the temperature decreases by the same amount
+/// every chain and reaches 0 after `n_levels` chains
+pub struct LinearCooling {
+    pub n_levels: usize,
+}
+
+impl CoolingSchedule for LinearCooling {
+    fn name(&self) -> &'static str {
+        "linear"
+    }
+    fn next_temperature(&mut self, _temperature: f32, stats: &ChainStats) -> f32 {
+        let fraction = stats.level as f32 / self.n_levels.max(1) as f32;
+        (stats.initial_temperature * (1.0 - fraction)).max(0.0)
+    }
+}
+
+/// Lundy-Mees cooling: T <- T / (1 + beta * T), with beta chosen
+/// to reach `final_temperature` after `n_levels` chains
+pub struct LundyMeesCooling {
+    pub n_levels: usize,
+    pub final_temperature: f32,
+}
+
+impl CoolingSchedule for LundyMeesCooling {
+    fn name(&self) -> &'static str {
+        "lundy-mees"
+    }
+    fn next_temperature(&mut self, temperature: f32, stats: &ChainStats) -> f32 {
+        let (initial, last) = (stats.initial_temperature, self.final_temperature);
+        let beta = ((initial - last) / (self.n_levels.max(1) as f32 * initial * last)).max(0.0);
+        temperature / (1.0 + beta * temperature)
+    }
+}
+
+/// Logarithmic cooling: T_k = T_0 * ln(2) / ln(k + 2). It cools very
+/// slowly, runs should be limited by `StoppingRule::max_levels`.
+pub struct LogarithmicCooling;
+
+impl CoolingSchedule for LogarithmicCooling {
+    fn name(&self) -> &'static str {
+        "logarithmic"
+    }
+    fn next_temperature(&mut self, _temperature: f32, stats: &ChainStats) -> f32 {
+        stats.initial_temperature * 2f32.ln() / (stats.level as f32 + 2.0).ln()
+    }
+}
+
+/// Adaptive cooling by acceptance rate feedback: cools fast while
+/// more moves than the target rate are accepted, slowly otherwise
+pub struct AdaptiveCooling {
+    pub target_acceptance: f64,
+    pub fast_alpha: f32,
+    pub slow_alpha: f32,
+}
+
+impl CoolingSchedule for AdaptiveCooling {
+    fn name(&self) -> &'static str {
+        "adaptive"
+    }
+    fn next_temperature(&mut self, temperature: f32, stats: &ChainStats) -> f32 {
+        if stats.acceptance_rate > self.target_acceptance {
+            temperature * self.fast_alpha
+        } else {
+            temperature * self.slow_alpha
+        }
+    }
+}
+
+/// Reheating: follows another schedule until the search freezes (the
+/// acceptance rate falls below `min_acceptance`), then restarts it from
+/// a fraction of the last starting temperature, at most `max_reheats` times
+pub struct ReheatingCooling {
+    pub schedule: Box<dyn CoolingSchedule>,
+    pub min_acceptance: f64,
+    pub reheat_fraction: f32,
+    pub max_reheats: usize,
+    reheats: usize,
+    /// The level and the temperature of the last (re)start
+    restart: (usize, Option<f32>),
+}
+
+impl ReheatingCooling {
+    // Constructor
+    pub fn new(
+        schedule: Box<dyn CoolingSchedule>,
+        min_acceptance: f64,
+        reheat_fraction: f32,
+        max_reheats: usize,
+    ) -> ReheatingCooling {
+        ReheatingCooling {
+            schedule,
+            min_acceptance,
+            reheat_fraction,
+            max_reheats,
+            reheats: 0,
+            restart: (0, None),
+        }
+    }
+
+    /// Returns the number of reheats of the current run
+    pub fn get_reheats(&self) -> usize {
+        self.reheats
+    }
+}
+
+impl CoolingSchedule for ReheatingCooling {
+    fn name(&self) -> &'static str {
+        "reheating"
+    }
+    fn next_temperature(&mut self, temperature: f32, stats: &ChainStats) -> f32 {
+        let (restart_level, restart_temperature) = self.restart;
+        let restart_temperature = restart_temperature.unwrap_or(stats.initial_temperature);
+        if stats.acceptance_rate < self.min_acceptance && self.reheats < self.max_reheats {
+            self.reheats += 1;
+            let reheated = restart_temperature * self.reheat_fraction;
+            self.restart = (stats.level, Some(reheated));
+            return reheated;
+        }
+        // The schedule sees the annealing since the last restart
+        let restarted_stats = ChainStats {
+            level: stats.level - restart_level,
+            initial_temperature: restart_temperature,
+            acceptance_rate: stats.acceptance_rate,
+        };
+        self.schedule.next_temperature(temperature, &restarted_stats)
+    }
+    fn reset(&mut self) {
+        self.reheats = 0;
+        self.restart = (0, None);
+        self.schedule.reset();
+    }
+}
+
+/// Names of the schedules that can be built by `build_cooling_schedule`
+pub const COOLING_NAMES: [&str; 6] = [
+    "geometric",
+    "linear",
+    "lundy-mees",
+    "logarithmic",
+    "adaptive",
+    "reheating",
+];
+
+/// Builds a cooling schedule by name. `alpha` is the cooling factor of
+/// the geometric schedules, the other schedules use default parameters.
+/// The logarithmic schedule does not freeze, the caller has to limit
+/// the number of chains by the stopping rule.
+pub fn build_cooling_schedule(name: &str, alpha: f32) -> Option<Box<dyn CoolingSchedule>> {
+    match name {
+        "geometric" => Some(Box::new(GeometricCooling { alpha })),
+        "linear" => Some(Box::new(LinearCooling { n_levels: 100 })),
+        "lundy-mees" => Some(Box::new(LundyMeesCooling {
+            n_levels: 100,
+            final_temperature: 0.01,
+        })),
+        "logarithmic" => Some(Box::new(LogarithmicCooling)),
+        "adaptive" => Some(Box::new(AdaptiveCooling {
+            target_acceptance: 0.2,
+            fast_alpha: alpha * alpha,
+            slow_alpha: alpha,
+        })),
+        "reheating" => Some(Box::new(ReheatingCooling::new(
+            Box::new(GeometricCooling { alpha }),
+            0.001,
+            0.5,
+            3,
+        ))),
+        _ => None,
+    }
+}
+
+/// Calibration of the temperatures of simulated annealing from the
+/// deltas of random swaps of random solutions
+#[derive(Debug, Clone, Copy)]
+pub struct TemperatureCalibration {
+    /// The target acceptance ratio of worsening moves at the start
+    pub initial_acceptance: f64,
+    /// The target acceptance ratio of worsening moves at the end, if set
+    /// it gives the final temperature and the geometric cooling rate
+    pub final_acceptance: Option<f64>,
+    /// The number of chains from the initial to the final temperature
+    pub n_levels: usize,
+    /// The number of sampled moves
+    pub n_samples: usize,
+}
+
+impl Default for TemperatureCalibration {
+    fn default() -> Self {
+        TemperatureCalibration {
+            initial_acceptance: 0.95,
+            final_acceptance: None,
+            n_levels: 100,
+            n_samples: 1000,
+        }
+    }
+}
+
+/// Returns the temperature at which the mean acceptance probability
+/// exp(-delta / T) of the worsening deltas equals the target ratio,
+/// found by bisection. Without worsening deltas the temperature is 1.
+pub fn temperature_for_acceptance(deltas: &[i32], acceptance: f64) -> f32 {
+    let worsening: Vec<f64> = deltas.iter().filter(|&&d| d > 0).map(|&d| d as f64).collect();
+    let acceptance = acceptance.clamp(1e-6, 1.0 - 1e-6);
+    let max_delta = match worsening.iter().cloned().reduce(f64::max) {
+        Some(max_delta) => max_delta,
+        None => return 1.0,
+    };
+    let ratio = |t: f64| worsening.iter().map(|d| (-d / t).exp()).sum::<f64>() / worsening.len() as f64;
+    // Every move is accepted with at least the target probability at `high`
+    let (mut low, mut high) = (0.0, max_delta / -acceptance.ln());
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        if ratio(middle) < acceptance {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    high as f32
+}
+
+/// When simulated annealing stops, checked after every chain whatever
+/// the cooling schedule: when the search is frozen (below the minimum
+/// temperature without improvement for a number of chains) or when the
+/// number of chains or iterations reaches its limit
+#[derive(Debug, Clone, Copy)]
+pub struct StoppingRule {
+    /// The temperature below which the search can be frozen
+    pub min_temperature: f32,
+    /// The number of chains without improvement of a frozen search
+    pub no_improvement_chains: usize,
+    /// The maximum number of chains, if any
+    pub max_levels: Option<usize>,
+    /// The maximum number of iterations, if any
+    pub max_iterations: Option<usize>,
+}
+
+impl Default for StoppingRule {
+    fn default() -> Self {
+        StoppingRule {
+            min_temperature: 0.01,
+            no_improvement_chains: 8,
+            max_levels: None,
+            max_iterations: None,
+        }
+    }
+}
+
+impl StoppingRule {
+    /// Returns true if the annealing should stop after `level` chains of
+    /// length `chain_length`, `iterations` in total, `no_improvement` of
+    /// them since the last improving move, at the given temperature
+    pub fn should_stop(
+        &self,
+        temperature: f32,
+        level: usize,
+        iterations: usize,
+        no_improvement: usize,
+        chain_length: usize,
+    ) -> bool {
+        let frozen = temperature < self.min_temperature
+            && no_improvement >= chain_length * self.no_improvement_chains;
+        frozen
+            || self.max_levels.is_some_and(|max| level >= max)
+            || self.max_iterations.is_some_and(|max| iterations >= max)
+    }
+}
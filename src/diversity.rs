@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+
+use crate::solution::Solution;
+
+/// Diversity of a population of solutions (e.g. final solutions of runs)
+#[derive(Debug, Clone, Default)]
+pub struct DiversitySummary {
+    pub n_solutions: usize,
+    /// The number of distinct permutations
+    pub n_distinct: usize,
+    /// Mean, smallest and largest Hamming distance over all pairs
+    pub mean_hamming: f64,
+    pub min_hamming: usize,
+    pub max_hamming: usize,
+    /// Mean Cayley (swap) distance over all pairs
+    pub mean_cayley: f64,
+    /// Mean overlap similarity over all pairs
+    pub mean_overlap: f64,
+    /// Mean entropy of the facilities assigned to every location,
+    /// normalized to [0, 1]: 0 if all solutions agree, 1 if the
+    /// assignments are uniformly spread
+    pub assignment_entropy: f64,
+}
+
+impl DiversitySummary {
+    /// Computes the pairwise distances and the assignment entropy.
+    /// All solutions must have the same size.
+    pub fn new(solutions: &[Solution]) -> DiversitySummary {
+        let n_solutions = solutions.len();
+        let n_distinct = solutions
+            .iter()
+            .map(|s| &s.solution_array)
+            .collect::<HashSet<_>>()
+            .len();
+        let mut summary = DiversitySummary {
+            n_solutions,
+            n_distinct,
+            ..Default::default()
+        };
+        if n_solutions < 2 {
+            summary.mean_overlap = 1.0;
+            return summary;
+        }
+
+        let mut pairs = 0;
+        let (mut hamming, mut cayley, mut overlap) = (0, 0, 0.0);
+        summary.min_hamming = usize::MAX;
+        for (i, a) in solutions.iter().enumerate() {
+            for b in &solutions[i + 1..] {
+                let distance = a.hamming_distance(b);
+                summary.min_hamming = summary.min_hamming.min(distance);
+                summary.max_hamming = summary.max_hamming.max(distance);
+                hamming += distance;
+                cayley += a.cayley_distance(b);
+                overlap += a.overlap(b);
+                pairs += 1;
+            }
+        }
+        summary.mean_hamming = hamming as f64 / pairs as f64;
+        summary.mean_cayley = cayley as f64 / pairs as f64;
+        summary.mean_overlap = overlap / pairs as f64;
+        summary.assignment_entropy = assignment_entropy(solutions);
+        summary
+    }
+}
+
+/// Returns the mean normalized entropy of the facilities assigned
+/// to every location over the solutions
+fn assignment_entropy(solutions: &[Solution]) -> f64 {
+    let n = solutions[0].solution_array.len();
+    // The entropy can not exceed ln of the number of solutions
+    let max_entropy = (n.min(solutions.len()) as f64).ln();
+    if n == 0 || max_entropy == 0.0 {
+        return 0.0;
+    }
+    let mut counts = vec![0; n];
+    let mut total = 0.0;
+    for location in 0..n {
+        counts.iter_mut().for_each(|c| *c = 0);
+        for solution in solutions {
+            counts[solution.solution_array[location]] += 1;
+        }
+        let entropy: f64 = counts
+            .iter()
+            .filter(|&&c| c > 0)
+            .map(|&c| {
+                let p = c as f64 / solutions.len() as f64;
+                -p * p.ln()
+            })
+            .sum();
+        total += entropy / max_entropy;
+    }
+    total / n as f64
+}
@@ -1,87 +1,486 @@
+use crate::checkpoint::{Checkpoint, StateSnapshot};
+use crate::diversity::DiversitySummary;
+use crate::qap_problem::QapProblem;
+use crate::run_length::{RunLengthDistribution, Target};
 use crate::solution::Solution;
 use crate::solvers::solver::Solver;
+use crate::stats::{pairwise_tests, save_pairwise_comparisons, ResultSet};
+use crate::summary::{save_summaries, summaries_to_markdown, RunSummary};
+use crate::trace::{Trace, TracePoint};
 
-use csv::Writer;
+use csv::{ReaderBuilder, Writer, WriterBuilder};
 use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::path::Path;
 use std::time::Instant;
 use meansd::MeanSD;
 
+/// A single restart performed within a time-limited run
+#[derive(Debug, Clone)]
+pub struct RestartRecord {
+    /// Index of the restart within the run
+    pub restart: usize,
+    /// Evaluation of the solution returned by the restart
+    pub evaluation: i32,
+    pub iterations: i32,
+    pub updates: i32,
+    /// Time (ms) since the start of the run when the restart finished
+    pub time: u128,
+}
+
+/// Column names of the csv file with results
+const RESULTS_HEADER: [&str; 10] = [
+    "run", "final_solution", "final_evaluation", "initial_solution",
+    "initial_evaluation", "iterations", "updates", "time",
+    "restarts", "time_to_best",
+];
+
+/// Evaluation written in place of a missing initial solution
+const MISSING_EVALUATION: i32 = 100000000;
+
+/// Parses a solution array written as "[1, 2, 3]"
+fn parse_solution_array(text: &str) -> Result<Vec<usize>, Box<dyn Error>> {
+    let text = text.trim().trim_start_matches('[').trim_end_matches(']');
+    let mut solution_array = vec![];
+    for value in text.split(',').filter(|v| !v.trim().is_empty()) {
+        solution_array.push(value.trim().parse()?);
+    }
+    Ok(solution_array)
+}
+
+/// Results of all runs of an experiment, detached from the solver,
+/// so they can be summarised and compared after the experiment
+#[derive(Debug, Clone, Default)]
+pub struct ExperimentResults {
+    /// To store final solutions
+    pub final_solutions: Vec<Solution>,
+    /// To store initial solutions
+    pub initial_solutions: Vec<Option<Solution>>,
+    pub iterations: Vec<i32>,
+    pub updates: Vec<i32>,
+    pub elapsed_time: Vec<u128>,
+    /// The number of solver restarts in each run
+    pub restarts: Vec<usize>,
+    /// Time (ms) since the start of the run when its best solution was found
+    pub time_to_best: Vec<u128>,
+    /// Per-restart records of each run
+    pub restart_records: Vec<Vec<RestartRecord>>,
+    /// Best-so-far traces of each run, empty if traces were not recorded
+    pub traces: Vec<Vec<TracePoint>>,
+}
+
+impl ExperimentResults {
+    /// Returns the number of stored runs
+    pub fn n_runs(&self) -> usize {
+        self.final_solutions.len()
+    }
+
+    /// Returns the final evaluations of all runs
+    pub fn final_evaluations(&self) -> Vec<i32> {
+        self.final_solutions.iter().map(|s| s.get_eval()).collect()
+    }
+
+    /// Returns the diversity of the final solutions of all runs
+    pub fn diversity(&self) -> DiversitySummary {
+        DiversitySummary::new(&self.final_solutions)
+    }
+
+    /// Appends run `i` of other results
+    pub fn push_run_from(&mut self, other: &ExperimentResults, i: usize) {
+        self.final_solutions.push(other.final_solutions[i].clone());
+        self.initial_solutions.push(other.initial_solutions[i].clone());
+        self.iterations.push(other.iterations[i]);
+        self.updates.push(other.updates[i]);
+        self.elapsed_time.push(other.elapsed_time[i]);
+        self.restarts.push(other.restarts[i]);
+        self.time_to_best.push(other.time_to_best[i]);
+        self.restart_records.push(other.restart_records[i].clone());
+        self.traces.push(other.traces.get(i).cloned().unwrap_or_default());
+    }
+
+    /// Returns the csv record of run `i`, numbered as `run`
+    fn csv_record(&self, i: usize, run: usize) -> Vec<String> {
+        let (initial_solution_array, initial_solution_eval) = match &self.initial_solutions[i] {
+            Some(initial_solution) => (initial_solution.solution_array.clone(), initial_solution.get_eval()),
+            None => (vec![0; 1], MISSING_EVALUATION),
+        };
+        vec![
+            run.to_string(),
+            format!("{:?}", &self.final_solutions[i].solution_array),
+            self.final_solutions[i].get_eval().to_string(),
+            format!("{:?}", initial_solution_array),
+            initial_solution_eval.to_string(),
+            self.iterations[i].to_string(),
+            self.updates[i].to_string(),
+            self.elapsed_time[i].to_string(),
+            self.restarts[i].to_string(),
+            self.time_to_best[i].to_string(),
+        ]
+    }
+
+    /// Appends run `i` to a csv file, the header is written if the file is new
+    pub fn append_run_to_csv(&self, i: usize, run: usize, path: &str) -> Result<(), Box<dyn Error>> {
+        let is_new = !Path::new(path).exists();
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut wtr = WriterBuilder::new().has_headers(false).from_writer(file);
+        if is_new {
+            wtr.write_record(RESULTS_HEADER)?;
+        }
+        wtr.write_record(self.csv_record(i, run))?;
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// Reads results saved by Experiment::save_results. Returns the run
+    /// numbers and the results. The restart records are not saved, so each
+    /// run gets a single record with its best solution at time_to_best.
+    pub fn read_csv(path: &str) -> Result<(Vec<usize>, ExperimentResults), Box<dyn Error>> {
+        let mut rdr = ReaderBuilder::new().from_path(path)?;
+        let headers = rdr.headers()?.clone();
+        let column = |name: &str| headers.iter().position(|h| h == name);
+        let required = |name: &str| column(name).ok_or(format!("{}: missing column {}", path, name));
+        let (run_col, final_col, final_eval_col) = (required("run")?, required("final_solution")?, required("final_evaluation")?);
+        let (initial_col, initial_eval_col) = (required("initial_solution")?, required("initial_evaluation")?);
+        let (iterations_col, updates_col, time_col) = (required("iterations")?, required("updates")?, required("time")?);
+        let (restarts_col, time_to_best_col) = (column("restarts"), column("time_to_best"));
+
+        let mut runs = vec![];
+        let mut results = ExperimentResults::default();
+        for record in rdr.records() {
+            let record = record?;
+            let mut final_solution = Solution::new(parse_solution_array(&record[final_col])?);
+            final_solution.set_eval(record[final_eval_col].parse()?);
+            let initial_eval: i32 = record[initial_eval_col].parse()?;
+            let initial_solution = if initial_eval == MISSING_EVALUATION {
+                None
+            } else {
+                let mut initial_solution = Solution::new(parse_solution_array(&record[initial_col])?);
+                initial_solution.set_eval(initial_eval);
+                Some(initial_solution)
+            };
+            let iterations: i32 = record[iterations_col].parse()?;
+            let updates: i32 = record[updates_col].parse()?;
+            let elapsed: u128 = record[time_col].parse()?;
+            let restarts: usize = match restarts_col {
+                Some(col) => record[col].parse()?,
+                None => 1,
+            };
+            let time_to_best: u128 = match time_to_best_col {
+                Some(col) => record[col].parse()?,
+                None => elapsed,
+            };
+            runs.push(record[run_col].parse()?);
+            results.restart_records.push(vec![RestartRecord {
+                restart: 0,
+                evaluation: final_solution.get_eval(),
+                iterations,
+                updates,
+                time: time_to_best,
+            }]);
+            results.final_solutions.push(final_solution);
+            results.initial_solutions.push(initial_solution);
+            results.iterations.push(iterations);
+            results.updates.push(updates);
+            results.elapsed_time.push(elapsed);
+            results.restarts.push(restarts);
+            results.time_to_best.push(time_to_best);
+            results.traces.push(vec![]);
+        }
+        Ok((runs, results))
+    }
+
+    /// Reads the traces saved by Experiment::save_traces,
+    /// runs are matched by their index
+    pub fn read_traces_csv(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let mut traces = vec![vec![]; self.n_runs()];
+        let mut rdr = ReaderBuilder::new().from_path(path)?;
+        for record in rdr.records() {
+            let record = record?;
+            let run: usize = record[0].parse()?;
+            if let Some(trace) = traces.get_mut(run) {
+                trace.push(TracePoint {
+                    time: record[1].parse()?,
+                    evaluations: record[2].parse()?,
+                    evaluation: record[3].parse()?,
+                });
+            }
+        }
+        self.traces = traces;
+        Ok(())
+    }
+
+    /// Returns, for each run, the time (ms) at which a solution with
+    /// evaluation <= `target` was first returned, or None if the run
+    /// never reached the target
+    pub fn time_to_target(&self, target: i32) -> Vec<Option<u128>> {
+        self.restart_records
+            .iter()
+            .map(|records| {
+                records
+                    .iter()
+                    .find(|record| record.evaluation <= target)
+                    .map(|record| record.time)
+            })
+            .collect()
+    }
+
+    /// Returns, for each run, the first point of its trace with
+    /// evaluation <= `target`, or None if the run never reached the
+    /// target or its trace was not recorded
+    pub fn first_hits(&self, target: i32) -> Vec<Option<TracePoint>> {
+        self.traces
+            .iter()
+            .map(|trace| trace.iter().find(|point| point.evaluation <= target).copied())
+            .collect()
+    }
+}
+
+/// Reads the runs saved in the checkpoint csv file. Rows of runs not marked
+/// completed in the manifest (the process stopped between appending the
+/// row and marking the run) and repeated rows are dropped from the file,
+/// so the repeated runs do not duplicate them.
+fn read_completed_runs(checkpoint: &Checkpoint) -> Result<(Vec<usize>, ExperimentResults), Box<dyn Error>> {
+    let path = checkpoint.results_path();
+    if !Path::new(&path).exists() {
+        return Ok((vec![], ExperimentResults::default()));
+    }
+    let (saved_runs, saved_results) = ExperimentResults::read_csv(&path)?;
+    let mut runs = vec![];
+    let mut results = ExperimentResults::default();
+    for (row, &run) in saved_runs.iter().enumerate() {
+        if checkpoint.is_completed(run) && !runs.contains(&run) {
+            runs.push(run);
+            results.push_run_from(&saved_results, row);
+        }
+    }
+    if runs.len() < saved_runs.len() {
+        fs::remove_file(&path)?;
+        for (row, &run) in runs.iter().enumerate() {
+            results.append_run_to_csv(row, run, &path)?;
+        }
+    }
+    Ok((runs, results))
+}
+
 /// An experiment object stores a solver, runs an experiment
 /// measures time, and saves a csv file with results
 pub struct Experiment<'a> {
     solver: &'a mut dyn Solver,
     /// How many times the experiment should be ran
     n_runs: usize,
-    /// To store final solutions
-    final_solutions: Vec<Solution>,
-    /// To store initial solutions
-    initial_solutions: Vec<Option<Solution>>,
-    iterations: Vec<i32>,
-    updates: Vec<i32>,
-    elapsed_time: Vec<u128>,
+    results: ExperimentResults,
+    /// Saves completed runs and solver states, if set
+    checkpoint: Option<Checkpoint>,
+    /// Whether best-so-far traces of the runs are recorded
+    record_traces: bool,
 }
 
 impl<'a> Experiment<'a> {
     pub fn new(solver: &'a mut dyn Solver, n_runs: usize) -> Self {
-        let final_solutions: Vec<Solution> = Vec::with_capacity(n_runs);
-        let initial_solutions: Vec<Option<Solution>> = Vec::with_capacity(n_runs);
-        let iterations: Vec<i32> = Vec::with_capacity(n_runs);
-        let updates: Vec<i32> = Vec::with_capacity(n_runs);
-        let elapsed_time: Vec<u128> = Vec::with_capacity(n_runs);
+        let results = ExperimentResults::default();
         Experiment {
             solver,
             n_runs,
-            final_solutions,
-            initial_solutions,
-            iterations,
-            updates,
-            elapsed_time,
+            results,
+            checkpoint: None,
+            record_traces: false,
         }
     }
+
+    /// Enables recording best-so-far traces of the runs,
+    /// needed for convergence curves and run-length distributions
+    pub fn set_record_traces(&mut self, record_traces: bool) {
+        self.record_traces = record_traces
+    }
+
+    /// Enables checkpointing of run(): every completed run is appended to
+    /// the checkpoint csv file and recorded in the manifest. Completed runs
+    /// are read back instead of being ran again, and an interrupted run is
+    /// continued from the last solver state snapshot if the solver supports it.
+    /// run_with_timelimit() does not use the checkpoint.
+    pub fn set_checkpoint(&mut self, checkpoint: Checkpoint) {
+        self.checkpoint = Some(checkpoint)
+    }
+
+    /// Returns the results of the last run() or run_with_timelimit()
+    pub fn get_results(&self) -> &ExperimentResults {
+        &self.results
+    }
+
     /// Runs solver n times, measures time,
     /// and saves statistics
     pub fn run(&mut self) -> () {
+        self.results = ExperimentResults::default();
+        // Runs completed before an interruption
+        let (completed_runs, completed_results) = match &self.checkpoint {
+            Some(checkpoint) => read_completed_runs(checkpoint).unwrap_or_else(|err| {
+                eprintln!("Could not read the checkpoint: {}", err);
+                (vec![], ExperimentResults::default())
+            }),
+            None => (vec![], ExperimentResults::default()),
+        };
         for i in 0..self.n_runs {
+            if let Some(checkpoint) = &self.checkpoint {
+                let completed = completed_runs.iter().position(|&run| run == i);
+                if let (true, Some(row)) = (checkpoint.is_completed(i), completed) {
+                    println!("Algorithm iteration {}; Completed before", i);
+                    self.results.push_run_from(&completed_results, row);
+                    continue;
+                }
+            }
 
             let start = Instant::now();
-            let solution = self.solver.solve();
+            let mut elapsed_offset = 0;
+            let mut resumed_solution = None;
+            if let Some(checkpoint) = &self.checkpoint {
+                let state = StateSnapshot::load(&checkpoint.state_path(i));
+                if let Some((offset, _)) = &state {
+                    elapsed_offset = *offset;
+                }
+                self.solver.set_state_snapshot(Some(checkpoint.state_snapshot(i, elapsed_offset)));
+                if let Some((_, state)) = state {
+                    if self.record_traces {
+                        // The trace covers only the resumed part of the run
+                        self.solver.set_trace(Some(Trace::starting_at(elapsed_offset)));
+                    }
+                    resumed_solution = self.solver.resume(&state);
+                    if resumed_solution.is_none() {
+                        elapsed_offset = 0;
+                    }
+                }
+            }
+            let solution = match resumed_solution {
+                Some(solution) => solution,
+                None => {
+                    if self.record_traces {
+                        self.solver.set_trace(Some(Trace::new()));
+                    }
+                    self.solver.solve()
+                }
+            };
+            let trace = self.solver.take_trace().map(Trace::into_points).unwrap_or_default();
             let initial_solution = self.solver.get_initial_solution();
-            let elapsed = start.elapsed().as_millis();
+            let elapsed = elapsed_offset + start.elapsed().as_millis();
 
             let n_iterations = self.solver.get_iter_count();
             let n_updates = self.solver.get_update_count();
             println!("Algorithm iteration {}; Best found solution evaluation {}", i, solution.get_eval());
-            self.final_solutions.push(solution);
-            self.initial_solutions.push(initial_solution);
-            self.iterations.push(n_iterations);
-            self.updates.push(n_updates);
-            self.elapsed_time.push(elapsed);
-            
+            let results = &mut self.results;
+            results.restart_records.push(vec![RestartRecord {
+                restart: 0,
+                evaluation: solution.get_eval(),
+                iterations: n_iterations,
+                updates: n_updates,
+                time: elapsed,
+            }]);
+            results.final_solutions.push(solution);
+            results.initial_solutions.push(initial_solution);
+            results.iterations.push(n_iterations);
+            results.updates.push(n_updates);
+            results.elapsed_time.push(elapsed);
+            results.restarts.push(1);
+            results.time_to_best.push(elapsed);
+            results.traces.push(trace);
+
+            if let Some(checkpoint) = &mut self.checkpoint {
+                let row = results.n_runs() - 1;
+                let saved = results
+                    .append_run_to_csv(row, i, &checkpoint.results_path())
+                    .and_then(|_| checkpoint.mark_completed(i));
+                if let Err(err) = saved {
+                    eprintln!("Could not save the checkpoint: {}", err);
+                }
+            }
+        }
+        if self.checkpoint.is_some() {
+            self.solver.set_state_snapshot(None);
         }
     }
 
-    /// Runs solver with time limit. Each of the n runs is limited.
+    /// Runs solver with time limit. Each of the n runs is a multi-start:
+    /// the solver is restarted until `limit` ms have passed (at least once)
+    /// and the best solution of the run is kept. Iterations and updates
+    /// are summed over the restarts of a run. The checkpoint is not used,
+    /// all runs are ran again after an interruption.
     pub fn run_with_timelimit(&mut self, limit: u128) -> () {
-        for _ in 0..self.n_runs {
+        self.results = ExperimentResults::default();
+        for i in 0..self.n_runs {
             let start = Instant::now();
+            let mut records: Vec<RestartRecord> = vec![];
+            let mut best_solution: Option<Solution> = None;
+            let mut best_initial_solution: Option<Solution> = None;
+            let mut best_time: u128 = 0;
             let mut n_iterations = 0;
+            let mut n_updates = 0;
             let mut elapsed = 0;
-            while elapsed < limit {
-                elapsed = start.elapsed().as_millis();
+            // A single trace spans all restarts of the run
+            let mut trace = if self.record_traces { Some(Trace::new()) } else { None };
+            while records.is_empty() || elapsed < limit {
+                self.solver.set_trace(trace.take());
                 let solution = self.solver.solve();
-                n_iterations = n_iterations + 1;
-                println!("Algorithm iteration {}; Best found solution evaluation {}", n_iterations, solution.get_eval());
-                self.final_solutions.push(solution);
+                trace = self.solver.take_trace();
+                elapsed = start.elapsed().as_millis();
+                let record = RestartRecord {
+                    restart: records.len(),
+                    evaluation: solution.get_eval(),
+                    iterations: self.solver.get_iter_count(),
+                    updates: self.solver.get_update_count(),
+                    time: elapsed,
+                };
+                n_iterations += record.iterations;
+                n_updates += record.updates;
+                let is_better = match &best_solution {
+                    Some(best) => solution.get_eval() < best.get_eval(),
+                    None => true,
+                };
+                if is_better {
+                    best_solution = Some(solution);
+                    best_initial_solution = self.solver.get_initial_solution();
+                    best_time = elapsed;
+                }
+                records.push(record);
             }
-            self.iterations.push(n_iterations);
-            self.elapsed_time.push(elapsed);
+            let solution = best_solution.unwrap();
+            println!(
+                "Algorithm iteration {}; Restarts {}; Best found solution evaluation {}",
+                i,
+                records.len(),
+                solution.get_eval()
+            );
+            let results = &mut self.results;
+            results.final_solutions.push(solution);
+            results.initial_solutions.push(best_initial_solution);
+            results.iterations.push(n_iterations);
+            results.updates.push(n_updates);
+            results.elapsed_time.push(elapsed);
+            results.restarts.push(records.len());
+            results.time_to_best.push(best_time);
+            results.restart_records.push(records);
+            results.traces.push(trace.map(Trace::into_points).unwrap_or_default());
         }
     }
 
+    /// Returns the time-to-target and run-length distributions of the
+    /// last experiment for every target. Traces have to be recorded,
+    /// gap targets are skipped when the optimum is not known.
+    pub fn get_run_length_distributions(
+        &self,
+        instance: &str,
+        solver: &str,
+        targets: &[Target],
+        optimum: Option<i32>,
+    ) -> Vec<RunLengthDistribution> {
+        targets
+            .iter()
+            .filter_map(|&target| RunLengthDistribution::new(instance, solver, &self.results, target, optimum))
+            .collect()
+    }
+
     /// Returns the mean elapsed time of all runs
     /// The run finction should be ran first.
     pub fn get_mean_elapsed_time(&self) -> u128 {
-        return self.elapsed_time.iter().sum::<u128>() / self.elapsed_time.len() as u128
+        let elapsed_time = &self.results.elapsed_time;
+        return elapsed_time.iter().sum::<u128>() / elapsed_time.len() as u128
     }
 
     /// Returns the mean and standars deviation of final evaluations of all runs
@@ -89,7 +488,7 @@ impl<'a> Experiment<'a> {
     pub fn get_elapsed_time_mean_std(&self) -> (f64, f64) {
         let mut meansd = MeanSD::default();
         for i in 0..self.n_runs{
-            meansd.update(self.elapsed_time[i] as f64)
+            meansd.update(self.results.elapsed_time[i] as f64)
         }
         return (meansd.mean(), meansd.sstdev())
     }
@@ -99,47 +498,187 @@ impl<'a> Experiment<'a> {
     pub fn get_final_evaluation_mean_std(&self) -> (f64, f64) {
         let mut meansd = MeanSD::default();
         for i in 0..self.n_runs{
-            meansd.update(self.final_solutions[i].get_eval() as f64)
+            meansd.update(self.results.final_solutions[i].get_eval() as f64)
         }
         return (meansd.mean(), meansd.sstdev())
     }
 
+    /// Returns the diversity of the final solutions of all runs
+    /// The run finction should be ran first.
+    pub fn get_diversity(&self) -> DiversitySummary {
+        self.results.diversity()
+    }
+
     pub fn print_results(&self) {
         for i in 0..self.n_runs {
             println!(
                 "Run {}, solution {:?}, eval {}, iter {}",
                 i,
-                self.final_solutions[i].solution_array,
-                self.final_solutions[i].get_eval(),
-                self.iterations[i]
+                self.results.final_solutions[i].solution_array,
+                self.results.final_solutions[i].get_eval(),
+                self.results.iterations[i]
             );
         }
+        let diversity = self.get_diversity();
+        println!(
+            "Distinct solutions {}, mean Hamming distance {:.2}, mean Cayley distance {:.2}, assignment entropy {:.3}",
+            diversity.n_distinct, diversity.mean_hamming, diversity.mean_cayley, diversity.assignment_entropy
+        );
     }
     /// Saves results in a csv file  according to the provided path
     pub fn save_results(&self, path: &String) -> Result<(), Box<dyn Error>> {
         let mut wtr = Writer::from_path(path)?;
         // Write column names
-        wtr.write_record(&["run", "final_solution", "final_evaluation", "initial_solution",
-                           "initial_evaluation", "iterations", "updates","time"])?;
+        wtr.write_record(RESULTS_HEADER)?;
         for i in 0..self.n_runs {
-            let mut initial_solution_array: &Vec<usize>= &vec![0; 1];
-            let mut initial_solution_eval: i32 = 100000000;
-            if !self.initial_solutions[i].is_none(){
-                initial_solution_array = &self.initial_solutions[i].as_ref().unwrap().solution_array;
-                initial_solution_eval = self.initial_solutions[i].as_ref().unwrap().get_eval();
+            wtr.write_record(self.results.csv_record(i, i))?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// Saves a record of every restart of every run in a csv file
+    /// according to the provided path
+    pub fn save_restart_records(&self, path: &String) -> Result<(), Box<dyn Error>> {
+        let mut wtr = Writer::from_path(path)?;
+        // Write column names
+        wtr.write_record(["run", "restart", "evaluation", "iterations", "updates", "time"])?;
+        for (i, records) in self.results.restart_records.iter().enumerate() {
+            for record in records {
+                wtr.write_record(&[
+                    i.to_string(),
+                    record.restart.to_string(),
+                    record.evaluation.to_string(),
+                    record.iterations.to_string(),
+                    record.updates.to_string(),
+                    record.time.to_string(),
+                ])?;
             }
-            wtr.write_record(&[
-                i.to_string(),
-                format!("{:?}", &self.final_solutions[i].solution_array),
-                self.final_solutions[i].get_eval().to_string(),
-                format!("{:?}", initial_solution_array),
-                initial_solution_eval.to_string(),
-                self.iterations[i].to_string(),
-                self.updates[i].to_string(),
-                self.elapsed_time[i].to_string(),
-            ])?;
         }
         wtr.flush()?;
         Ok(())
     }
+
+    /// Saves the best-so-far traces of all runs in a csv file
+    /// according to the provided path
+    pub fn save_traces(&self, path: &String) -> Result<(), Box<dyn Error>> {
+        let mut wtr = Writer::from_path(path)?;
+        // Write column names
+        wtr.write_record(["run", "time", "evaluations", "evaluation"])?;
+        for (i, trace) in self.results.traces.iter().enumerate() {
+            for point in trace {
+                wtr.write_record(&[
+                    i.to_string(),
+                    point.time.to_string(),
+                    point.evaluations.to_string(),
+                    point.evaluation.to_string(),
+                ])?;
+            }
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+/// Settings of a comparison of solvers over instances
+#[derive(Debug, Clone)]
+pub struct ComparisonSettings {
+    /// Prefix of the summary and tests csv files
+    pub name: String,
+    /// Folder with instances. This folder should be inside ./data
+    pub data_folder: String,
+    /// The folder where the csv files are saved
+    pub results_folder: String,
+    /// How many times every solver is ran on every instance
+    pub n_runs: usize,
+    /// The gap (%) at which a run counts as successful
+    pub target_gap: f64,
+    /// Significance level of the statistical tests
+    pub alpha: f64,
+}
+
+impl Default for ComparisonSettings {
+    fn default() -> Self {
+        ComparisonSettings {
+            name: "comparison".to_string(),
+            data_folder: "qapdatsol".to_string(),
+            results_folder: "results".to_string(),
+            n_runs: 10,
+            target_gap: 1.0,
+            alpha: 0.05,
+        }
+    }
+}
+
+/// Runs every configuration, built by `make_solver` from its label, on
+/// every instance. The runs of each configuration on each instance are saved
+/// in `<instance>_<label>.csv`, the summaries in `<name>_summary.csv` and the
+/// Mann-Whitney and Wilcoxon tests of the final evaluations in
+/// `<name>_tests.csv`. Returns the summaries.
+pub fn run_comparison<F>(
+    settings: &ComparisonSettings,
+    instances: &[&str],
+    configs: &[&str],
+    mut make_solver: F,
+) -> Vec<RunSummary>
+where
+    F: for<'p> FnMut(&str, &'p mut QapProblem) -> Result<Box<dyn Solver + 'p>, String>,
+{
+    let results_folder = Path::new(".").join(&settings.results_folder);
+    let mut summaries = vec![];
+    let mut result_sets = vec![];
+    for &instance_filename in instances {
+        let instance_path = Path::new(".")
+            .join("data")
+            .join(&settings.data_folder)
+            .join(instance_filename)
+            .to_string_lossy()
+            .to_string();
+        println!("{}", instance_filename);
+        let sln_path = Path::new(&instance_path).with_extension("sln");
+        let optimum = Solution::read_sln(&sln_path.to_string_lossy()).ok().map(|s| s.get_eval());
+        let mut qap_problem = match QapProblem::new(&instance_path) {
+            Ok(qap_problem) => qap_problem,
+            Err(err) => {
+                eprintln!("Error: {}", err);
+                continue;
+            }
+        };
+        for &label in configs {
+            let mut solver = match make_solver(label, &mut qap_problem) {
+                Ok(solver) => solver,
+                Err(err) => {
+                    eprintln!("Error: {}", err);
+                    continue;
+                }
+            };
+            let mut experiment = Experiment::new(&mut *solver, settings.n_runs);
+            experiment.run();
+            let path = results_folder
+                .join(format!("{}_{}.csv", instance_filename, label))
+                .to_string_lossy()
+                .to_string();
+            let _ = experiment.save_results(&path);
+            let results = experiment.get_results();
+            summaries.push(RunSummary::new(instance_filename, label, results, optimum, settings.target_gap));
+            result_sets.push(ResultSet::new(instance_filename, label, results));
+        }
+    }
+    println!("{}", summaries_to_markdown(&summaries));
+    for summary in &summaries {
+        println!(
+            "{} {}: mean iterations {:.0}, mean updates {:.1}",
+            summary.instance, summary.solver, summary.mean_iterations, summary.mean_updates
+        );
+    }
+    let summary_path = results_folder.join(format!("{}_summary.csv", settings.name));
+    if let Err(err) = save_summaries(&summaries, &summary_path.to_string_lossy()) {
+        eprintln!("Error: {}", err);
+    }
+    let comparisons = pairwise_tests(&result_sets, settings.alpha);
+    let tests_path = results_folder.join(format!("{}_tests.csv", settings.name));
+    if let Err(err) = save_pairwise_comparisons(&comparisons, &tests_path.to_string_lossy()) {
+        eprintln!("Error: {}", err);
+    }
+    summaries
 }
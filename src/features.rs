@@ -0,0 +1,163 @@
+use csv::Writer;
+use std::error::Error;
+use std::fs;
+
+use crate::qap_problem::QapProblem;
+use crate::tuning::parameters::instance_class;
+
+/// Descriptive features of a matrix of an instance
+#[derive(Debug, Clone)]
+pub struct MatrixFeatures {
+    /// Coefficient of variation (%) of all entries, 100 * std / mean
+    pub dominance: f64,
+    /// Fraction of zero entries off the diagonal
+    pub sparsity: f64,
+    pub symmetric: bool,
+    /// True if any diagonal entry is non-zero
+    pub nonzero_diagonal: bool,
+    pub min: i32,
+    pub max: i32,
+}
+
+impl MatrixFeatures {
+    /// Computes the features of a square matrix
+    pub fn new(matrix: &[Vec<i32>]) -> MatrixFeatures {
+        let n = matrix.len();
+        let values = || matrix.iter().flatten().map(|&v| v as f64);
+        let count = (n * n).max(1) as f64;
+        let mean = values().sum::<f64>() / count;
+        // Population standard deviation, as in the QAPLIB dominance definition
+        let std = (values().map(|v| (v - mean).powi(2)).sum::<f64>() / count).sqrt();
+        let zeros = (0..n)
+            .flat_map(|i| (0..n).map(move |j| (i, j)))
+            .filter(|&(i, j)| i != j && matrix[i][j] == 0)
+            .count();
+        MatrixFeatures {
+            dominance: if mean == 0.0 { 0.0 } else { 100.0 * std / mean },
+            sparsity: if n > 1 { zeros as f64 / (n * (n - 1)) as f64 } else { 0.0 },
+            symmetric: (0..n).all(|i| (0..i).all(|j| matrix[i][j] == matrix[j][i])),
+            nonzero_diagonal: (0..n).any(|i| matrix[i][i] != 0),
+            min: matrix.iter().flatten().copied().min().unwrap_or(0),
+            max: matrix.iter().flatten().copied().max().unwrap_or(0),
+        }
+    }
+}
+
+/// Descriptive features of an instance, matrix A holds the
+/// distances between locations and matrix B the flows between facilities
+#[derive(Debug, Clone)]
+pub struct InstanceFeatures {
+    pub n: usize,
+    pub distances: MatrixFeatures,
+    pub flows: MatrixFeatures,
+    /// Fraction of triples of distinct locations (i, j, k)
+    /// with d(i, j) > d(i, k) + d(k, j)
+    pub triangle_violations: f64,
+}
+
+impl InstanceFeatures {
+    /// Computes the features of an instance
+    pub fn new(problem: &QapProblem) -> InstanceFeatures {
+        let matrix_a = problem.matrix_a_ref();
+        InstanceFeatures {
+            n: problem.get_n(),
+            distances: MatrixFeatures::new(matrix_a),
+            flows: MatrixFeatures::new(problem.matrix_b_ref()),
+            triangle_violations: triangle_violations(matrix_a),
+        }
+    }
+}
+
+/// Returns the fraction of triples of distinct indices violating
+/// the triangle inequality
+fn triangle_violations(matrix: &[Vec<i32>]) -> f64 {
+    let n = matrix.len();
+    if n < 3 {
+        return 0.0;
+    }
+    let mut violations: usize = 0;
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, &direct) in row.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            violations += (0..n)
+                .filter(|&k| k != i && k != j && direct > row[k] + matrix[k][j])
+                .count();
+        }
+    }
+    violations as f64 / (n * (n - 1) * (n - 2)) as f64
+}
+
+/// Computes the features of all instances (.dat files) in a folder,
+/// sorted by file name. Instances which can not be read are skipped.
+pub fn inspect(folder: &str) -> Result<Vec<(String, InstanceFeatures)>, Box<dyn Error>> {
+    let mut paths: Vec<_> = fs::read_dir(folder)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|e| e == "dat"))
+        .collect();
+    paths.sort();
+    let mut features = vec![];
+    for path in paths {
+        let instance = path.file_name().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        match QapProblem::new(&path.to_string_lossy()) {
+            Ok(problem) => features.push((instance, problem.features())),
+            Err(err) => eprintln!("Skipping {}: {}", instance, err),
+        }
+    }
+    Ok(features)
+}
+
+/// Column names of the features table
+const FEATURES_HEADER: [&str; 16] = [
+    "instance", "n", "distance_dominance", "flow_dominance", "distance_sparsity",
+    "flow_sparsity", "distance_symmetric", "flow_symmetric", "distance_nonzero_diagonal",
+    "flow_nonzero_diagonal", "distance_min", "distance_max", "flow_min", "flow_max",
+    "triangle_violations", "class",
+];
+
+/// Formats the features of an instance as a row of the features table
+fn features_row(instance: &str, features: &InstanceFeatures) -> Vec<String> {
+    let (a, b) = (&features.distances, &features.flows);
+    vec![
+        instance.to_string(),
+        features.n.to_string(),
+        format!("{:.2}", a.dominance),
+        format!("{:.2}", b.dominance),
+        format!("{:.3}", a.sparsity),
+        format!("{:.3}", b.sparsity),
+        a.symmetric.to_string(),
+        b.symmetric.to_string(),
+        a.nonzero_diagonal.to_string(),
+        b.nonzero_diagonal.to_string(),
+        a.min.to_string(),
+        a.max.to_string(),
+        b.min.to_string(),
+        b.max.to_string(),
+        format!("{:.4}", features.triangle_violations),
+        instance_class(instance),
+    ]
+}
+
+/// Saves the features of the instances in a csv file according to the provided path
+pub fn save_features(features: &[(String, InstanceFeatures)], path: &str) -> Result<(), Box<dyn Error>> {
+    let mut wtr = Writer::from_path(path)?;
+    // Write column names
+    wtr.write_record(FEATURES_HEADER)?;
+    for (instance, instance_features) in features {
+        wtr.write_record(features_row(instance, instance_features))?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Returns the features of the instances as a Markdown table
+pub fn features_to_markdown(features: &[(String, InstanceFeatures)]) -> String {
+    let mut table = format!("| {} |\n", FEATURES_HEADER.join(" | "));
+    table += &format!("|{}\n", "---|".repeat(FEATURES_HEADER.len()));
+    for (instance, instance_features) in features {
+        table += &format!("| {} |\n", features_row(instance, instance_features).join(" | "));
+    }
+    table
+}
+
@@ -0,0 +1,162 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+
+use crate::qap_problem::QapProblem;
+use crate::solution::Solution;
+use crate::utils::{arange, permute_array};
+
+/// Returns a symmetric n x n matrix with a zero diagonal,
+/// the entries above the diagonal are drawn by `entry`
+fn symmetric_matrix<F: FnMut() -> i32>(n: usize, mut entry: F) -> Vec<Vec<i32>> {
+    let upper: Vec<Vec<i32>> = (0..n).map(|i| (i + 1..n).map(|_| entry()).collect()).collect();
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| match i.cmp(&j) {
+                    Ordering::Less => upper[i][j - i - 1],
+                    Ordering::Greater => upper[j][i - j - 1],
+                    Ordering::Equal => 0,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the matrix of rounded Euclidean distances between the points
+fn euclidean_distances(points: &[(f64, f64)]) -> Vec<Vec<i32>> {
+    points
+        .iter()
+        .map(|&(x1, y1)| {
+            points
+                .iter()
+                .map(|&(x2, y2)| ((x1 - x2).powi(2) + (y1 - y2).powi(2)).sqrt().round() as i32)
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the matrix of Manhattan distances between the cells
+/// of a grid with the given number of rows and columns
+fn manhattan_distances(rows: usize, columns: usize) -> Vec<Vec<i32>> {
+    let n = rows * columns;
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| ((i / columns).abs_diff(j / columns) + (i % columns).abs_diff(j % columns)) as i32)
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns random sparse flows: every pair of facilities
+/// has a flow in 1..=max_flow with probability `density`
+fn sparse_flows(rng: &mut StdRng, n: usize, density: f64, max_flow: i32) -> Vec<Vec<i32>> {
+    symmetric_matrix(n, || {
+        if rng.gen_bool(density.clamp(0.0, 1.0)) {
+            rng.gen_range(1..=max_flow.max(1))
+        } else {
+            0
+        }
+    })
+}
+
+/// Uniform random instance in the style of Taillard's tai-a:
+/// distances and flows drawn uniformly from 0..=max_value
+pub fn uniform(n: usize, max_value: i32, seed: u64) -> QapProblem {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let matrix_a = symmetric_matrix(n, || rng.gen_range(0..=max_value));
+    let matrix_b = symmetric_matrix(n, || rng.gen_range(0..=max_value));
+    QapProblem::from_matrices(matrix_a, matrix_b).unwrap()
+}
+
+/// Structured instance in the style of tai-b: locations are clustered
+/// around centers on a 100 x 100 square, with Euclidean distances, and
+/// facilities form groups with heavy flows inside a group and light
+/// flows between groups
+pub fn clustered(n: usize, n_clusters: usize, seed: u64) -> QapProblem {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let n_clusters = n_clusters.clamp(1, n.max(1));
+    let centers: Vec<(f64, f64)> = (0..n_clusters)
+        .map(|_| (rng.gen_range(10.0..90.0), rng.gen_range(10.0..90.0)))
+        .collect();
+    let points: Vec<(f64, f64)> = (0..n)
+        .map(|i| {
+            let (x, y) = centers[i % n_clusters];
+            (x + rng.gen_range(-10.0..10.0), y + rng.gen_range(-10.0..10.0))
+        })
+        .collect();
+    let matrix_a = euclidean_distances(&points);
+
+    // The group of a facility is random, so that groups do not
+    // coincide with the clusters of locations with the same index
+    let mut group: Vec<usize> = (0..n).map(|i| i % n_clusters).collect();
+    permute_array(&mut rng, &mut group);
+    let mut matrix_b = vec![vec![0; n]; n];
+    for i in 0..n {
+        for j in i + 1..n {
+            let flow = if group[i] == group[j] {
+                rng.gen_range(10..=100)
+            } else if rng.gen_bool(0.2) {
+                rng.gen_range(1..=10)
+            } else {
+                0
+            };
+            matrix_b[i][j] = flow;
+            matrix_b[j][i] = flow;
+        }
+    }
+    QapProblem::from_matrices(matrix_a, matrix_b).unwrap()
+}
+
+/// Instance in the style of nug: locations are the cells of a grid
+/// with Manhattan distances, flows are sparse with the given density
+pub fn manhattan(rows: usize, columns: usize, density: f64, max_flow: i32, seed: u64) -> QapProblem {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let matrix_b = sparse_flows(&mut rng, rows * columns, density, max_flow);
+    QapProblem::from_matrices(manhattan_distances(rows, columns), matrix_b).unwrap()
+}
+
+/// Instance with uniform random distances in 1..=max_value and sparse
+/// flows, the fraction of pairs of facilities with a flow is `density`
+pub fn sparse(n: usize, density: f64, max_value: i32, seed: u64) -> QapProblem {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let matrix_a = symmetric_matrix(n, || rng.gen_range(1..=max_value.max(1)));
+    let matrix_b = sparse_flows(&mut rng, n, density, max_value);
+    QapProblem::from_matrices(matrix_a, matrix_b).unwrap()
+}
+
+/// Instance with a known optimum, returned along with the instance.
+/// Distances are uniform in 1..=max_value and the flow between the
+/// facilities of locations i and j of a random planted solution is a
+/// non-increasing function of the distance between i and j. The planted
+/// solution then pairs the largest distances with the smallest flows,
+/// which by the rearrangement inequality no other solution can improve.
+pub fn planted(n: usize, max_value: i32, seed: u64) -> (QapProblem, Solution) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let max_value = max_value.max(1);
+    let matrix_a = symmetric_matrix(n, || rng.gen_range(1..=max_value));
+
+    let mut solution_array = vec![0; n];
+    arange(&mut solution_array, 0, 1);
+    permute_array(&mut rng, &mut solution_array);
+    // Random non-increasing map from distances to flows
+    let mut steps: Vec<i32> = (0..=max_value).map(|_| rng.gen_range(0..=2)).collect();
+    for d in (0..max_value as usize).rev() {
+        steps[d] += steps[d + 1];
+    }
+    // Every solution maps the zero diagonal of A onto the zero diagonal
+    // of B, so only the entries off the diagonal matter for the bound
+    let mut matrix_b = vec![vec![0; n]; n];
+    for (i, row) in matrix_a.iter().enumerate() {
+        for (j, &distance) in row.iter().enumerate() {
+            if i != j {
+                matrix_b[solution_array[i]][solution_array[j]] = steps[distance as usize];
+            }
+        }
+    }
+    let problem = QapProblem::from_matrices(matrix_a, matrix_b).unwrap();
+    let mut solution = Solution::new(solution_array);
+    solution.evaluate(problem.matrix_a_ref(), problem.matrix_b_ref());
+    (problem, solution)
+}
@@ -0,0 +1,211 @@
+use csv::Writer;
+use rand::Rng;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::landscape::random_solution;
+use crate::qap_problem::QapProblem;
+use crate::solution::Solution;
+use crate::solvers::steepest_ls_solver::SteepestLSSolver;
+
+/// Settings of the local optima network sampling
+#[derive(Debug, Clone)]
+pub struct LonSettings {
+    /// The number of independent ILS runs
+    pub n_runs: usize,
+    /// An ILS run stops after this many perturbations without improvement
+    pub max_no_improvement: usize,
+    /// The number of random swaps of a perturbation, as a fraction of n
+    pub perturbation_strength: f64,
+}
+
+impl Default for LonSettings {
+    fn default() -> Self {
+        LonSettings {
+            n_runs: 20,
+            max_no_improvement: 100,
+            perturbation_strength: 0.1,
+        }
+    }
+}
+
+/// A local optimum of the network
+#[derive(Debug, Clone)]
+pub struct LonNode {
+    pub solution: Solution,
+    /// How many descents ended in the local optimum, an estimate
+    /// of the size of its basin of attraction
+    pub hits: usize,
+}
+
+/// Local optima network: nodes are local optima of the steepest descent
+/// over the swap neighbourhood, and a directed edge (a, b) with weight w
+/// means that w perturbations of a followed by a descent ended in b
+pub struct LocalOptimaNetwork {
+    pub nodes: Vec<LonNode>,
+    /// Edge weights by (source, target) node index
+    pub edges: BTreeMap<(usize, usize), usize>,
+    index: HashMap<Vec<usize>, usize>,
+}
+
+impl Default for LocalOptimaNetwork {
+    fn default() -> Self {
+        LocalOptimaNetwork::new()
+    }
+}
+
+impl LocalOptimaNetwork {
+    /// Constructor of an empty network
+    pub fn new() -> LocalOptimaNetwork {
+        LocalOptimaNetwork {
+            nodes: vec![],
+            edges: BTreeMap::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Records a descent ending in the local optimum, returns its node index
+    fn hit(&mut self, local_optimum: Solution) -> usize {
+        match self.index.get(&local_optimum.solution_array) {
+            Some(&i) => {
+                self.nodes[i].hits += 1;
+                i
+            }
+            None => {
+                let i = self.nodes.len();
+                self.index.insert(local_optimum.solution_array.clone(), i);
+                self.nodes.push(LonNode {
+                    solution: local_optimum,
+                    hits: 1,
+                });
+                i
+            }
+        }
+    }
+
+    /// Samples the network with iterated local search: every run starts
+    /// from a random local optimum, perturbs it with random swaps and
+    /// descends again, recording an escape edge every time. The search
+    /// moves to the new local optimum if it is not worse. Instances with
+    /// fewer than 2 locations have no swaps, their network is empty.
+    pub fn sample(problem: &QapProblem, settings: &LonSettings) -> LocalOptimaNetwork {
+        let mut rng = rand::thread_rng();
+        let mut solver = SteepestLSSolver::new(problem);
+        let mut network = LocalOptimaNetwork::new();
+        let n = problem.get_n();
+        if n < 2 {
+            return network;
+        }
+        let n_swaps = ((settings.perturbation_strength * n as f64).round() as usize).max(2);
+        for _ in 0..settings.n_runs {
+            let mut current = network.hit(solver.solve_steepest(random_solution(&mut rng, n)));
+            let mut no_improvement = 0;
+            while no_improvement < settings.max_no_improvement {
+                let mut perturbed = network.nodes[current].solution.clone();
+                for _ in 0..n_swaps {
+                    let i = rng.gen_range(0..n);
+                    let j = (i + rng.gen_range(1..n)) % n;
+                    perturbed.exchange_facilities(&[i.min(j), i.max(j)]);
+                }
+                let next = network.hit(solver.solve_steepest(perturbed));
+                *network.edges.entry((current, next)).or_insert(0) += 1;
+                let (current_eval, next_eval) =
+                    (network.nodes[current].solution.get_eval(), network.nodes[next].solution.get_eval());
+                if next_eval < current_eval {
+                    no_improvement = 0;
+                } else {
+                    no_improvement += 1;
+                }
+                if next_eval <= current_eval {
+                    current = next;
+                }
+            }
+        }
+        network
+    }
+
+    /// Returns the indices of sinks: explored nodes without an edge to a
+    /// better node. Every sink is the bottom of a funnel. Nodes that were
+    /// never perturbed have no outgoing edges and are not sinks.
+    pub fn sinks(&self) -> Vec<usize> {
+        let mut explored = vec![false; self.nodes.len()];
+        let mut has_improving_edge = vec![false; self.nodes.len()];
+        for &(source, target) in self.edges.keys() {
+            explored[source] = true;
+            if self.nodes[target].solution.get_eval() < self.nodes[source].solution.get_eval() {
+                has_improving_edge[source] = true;
+            }
+        }
+        (0..self.nodes.len()).filter(|&i| explored[i] && !has_improving_edge[i]).collect()
+    }
+
+    /// Returns the fraction of all descents ending in each node
+    fn basin_estimates(&self) -> Vec<f64> {
+        let total: usize = self.nodes.iter().map(|node| node.hits).sum();
+        self.nodes.iter().map(|node| node.hits as f64 / total.max(1) as f64).collect()
+    }
+
+    /// Saves the nodes and edges in two csv files
+    pub fn save_csv(&self, nodes_path: &str, edges_path: &str) -> Result<(), Box<dyn Error>> {
+        let sinks = self.sinks();
+        let mut wtr = Writer::from_path(nodes_path)?;
+        // Write column names
+        wtr.write_record(["id", "fitness", "hits", "basin", "sink", "solution"])?;
+        for (i, (node, basin)) in self.nodes.iter().zip(self.basin_estimates()).enumerate() {
+            wtr.write_record(&[
+                i.to_string(),
+                node.solution.get_eval().to_string(),
+                node.hits.to_string(),
+                basin.to_string(),
+                sinks.contains(&i).to_string(),
+                format!("{:?}", node.solution.solution_array),
+            ])?;
+        }
+        wtr.flush()?;
+
+        let mut wtr = Writer::from_path(edges_path)?;
+        wtr.write_record(["source", "target", "weight"])?;
+        for (&(source, target), weight) in &self.edges {
+            wtr.write_record(&[source.to_string(), target.to_string(), weight.to_string()])?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    /// Saves the network in the GraphML format
+    pub fn save_graphml(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let sinks = self.sinks();
+        let mut file = BufWriter::new(File::create(path)?);
+        writeln!(file, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(file, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+        writeln!(file, r#"  <key id="fitness" for="node" attr.name="fitness" attr.type="int"/>"#)?;
+        writeln!(file, r#"  <key id="hits" for="node" attr.name="hits" attr.type="int"/>"#)?;
+        writeln!(file, r#"  <key id="basin" for="node" attr.name="basin" attr.type="double"/>"#)?;
+        writeln!(file, r#"  <key id="sink" for="node" attr.name="sink" attr.type="boolean"/>"#)?;
+        writeln!(file, r#"  <key id="weight" for="edge" attr.name="weight" attr.type="int"/>"#)?;
+        writeln!(file, r#"  <graph id="lon" edgedefault="directed">"#)?;
+        for (i, (node, basin)) in self.nodes.iter().zip(self.basin_estimates()).enumerate() {
+            writeln!(
+                file,
+                r#"    <node id="n{}"><data key="fitness">{}</data><data key="hits">{}</data><data key="basin">{}</data><data key="sink">{}</data></node>"#,
+                i,
+                node.solution.get_eval(),
+                node.hits,
+                basin,
+                sinks.contains(&i)
+            )?;
+        }
+        for (&(source, target), weight) in &self.edges {
+            writeln!(
+                file,
+                r#"    <edge source="n{}" target="n{}"><data key="weight">{}</data></edge>"#,
+                source, target, weight
+            )?;
+        }
+        writeln!(file, "  </graph>\n</graphml>")?;
+        file.flush()?;
+        Ok(())
+    }
+}
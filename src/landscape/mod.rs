@@ -0,0 +1,275 @@
+pub mod lon;
+
+use csv::Writer;
+use meansd::MeanSD;
+use rand::rngs::ThreadRng;
+use rand::Rng;
+use std::collections::HashMap;
+use std::error::Error;
+
+use crate::qap_problem::QapProblem;
+use crate::solution::Solution;
+use crate::solvers::steepest_ls_solver::SteepestLSSolver;
+use crate::summary::relative_gap;
+use crate::utils::{arange, permute_array};
+
+/// Settings of the fitness landscape analysis
+#[derive(Debug, Clone)]
+pub struct LandscapeSettings {
+    /// The number of steps of the random walk
+    pub walk_length: usize,
+    /// The largest lag of the autocorrelation function
+    pub max_lag: usize,
+    /// The number of steepest descents from random solutions
+    pub n_descents: usize,
+}
+
+impl Default for LandscapeSettings {
+    fn default() -> Self {
+        LandscapeSettings {
+            walk_length: 10000,
+            max_lag: 10,
+            n_descents: 200,
+        }
+    }
+}
+
+/// Fitness landscape metrics of an instance over the swap neighbourhood
+#[derive(Debug, Clone)]
+pub struct LandscapeMetrics {
+    pub instance: String,
+    pub n: usize,
+    /// Autocorrelation of the random walk evaluations for lags 1..=max_lag
+    pub autocorrelation: Vec<f64>,
+    /// Correlation length -1 / ln|r(1)|
+    pub correlation_length: f64,
+    /// The number of steepest descents
+    pub n_descents: usize,
+    /// The number of distinct local optima found by the descents
+    pub n_local_optima: usize,
+    /// Distinct local optima per descent
+    pub local_optima_density: f64,
+    /// Mean and largest estimated basin size (fraction of descents
+    /// ending in the local optimum)
+    pub mean_basin_size: f64,
+    pub max_basin_size: f64,
+    /// Estimated basin size of the best local optimum found
+    pub best_basin_size: f64,
+    /// Mean gap (%) of the local optima to the optimum, if known
+    pub mean_local_optimum_gap: Option<f64>,
+    /// Fitness-distance correlation of the distinct local optima, if
+    /// the optimum is known
+    pub fitness_distance_correlation: Option<f64>,
+    /// Mean distance of the distinct local optima to the optimum, if known
+    pub mean_distance_to_optimum: Option<f64>,
+}
+
+/// Returns a random solution
+pub(crate) fn random_solution(rng: &mut ThreadRng, n: usize) -> Solution {
+    let mut solution_array = vec![0; n];
+    arange(&mut solution_array, 0, 1);
+    permute_array(rng, &mut solution_array);
+    Solution::new(solution_array)
+}
+
+/// Returns the evaluations along a random walk of random swaps
+pub fn random_walk(problem: &QapProblem, length: usize) -> Vec<f64> {
+    let mut rng = rand::thread_rng();
+    let n = problem.get_n();
+    let mut solution = random_solution(&mut rng, n);
+    let mut evaluation = solution.evaluate(problem.matrix_a_ref(), problem.matrix_b_ref());
+    let mut evaluations = Vec::with_capacity(length + 1);
+    evaluations.push(evaluation as f64);
+    for _ in 0..length {
+        let i = rng.gen_range(0..n);
+        let j = (i + rng.gen_range(1..n)) % n;
+        let pair = [i.min(j), i.max(j)];
+        evaluation += solution.calculate_delta(problem.matrix_a_ref(), problem.matrix_b_ref(), &pair);
+        solution.exchange_facilities(&pair);
+        evaluations.push(evaluation as f64);
+    }
+    evaluations
+}
+
+/// Returns the autocorrelation of a series at the given lag
+pub fn autocorrelation(series: &[f64], lag: usize) -> f64 {
+    if lag >= series.len() {
+        return f64::NAN;
+    }
+    let mean = series.iter().sum::<f64>() / series.len() as f64;
+    let variance: f64 = series.iter().map(|x| (x - mean).powi(2)).sum();
+    if variance == 0.0 {
+        return f64::NAN;
+    }
+    let covariance: f64 = series.windows(lag + 1).map(|w| (w[0] - mean) * (w[lag] - mean)).sum();
+    covariance / variance
+}
+
+/// Returns the correlation length -1 / ln|r(1)| of the
+/// autocorrelation at lag 1
+pub fn correlation_length(first_autocorrelation: f64) -> f64 {
+    -1.0 / first_autocorrelation.abs().ln()
+}
+
+/// Returns the Pearson correlation between fitness and distance
+pub fn fitness_distance_correlation(fitness: &[f64], distances: &[f64]) -> f64 {
+    let (mut f, mut d) = (MeanSD::default(), MeanSD::default());
+    for (&x, &y) in fitness.iter().zip(distances) {
+        f.update(x);
+        d.update(y);
+    }
+    // Sample covariance, to match the sample standard deviations
+    let covariance: f64 = fitness
+        .iter()
+        .zip(distances)
+        .map(|(x, y)| (x - f.mean()) * (y - d.mean()))
+        .sum::<f64>()
+        / (fitness.len() as f64 - 1.0);
+    covariance / (f.sstdev() * d.sstdev())
+}
+
+/// Distinct local optima reached by steepest descents
+/// from random solutions, with the number of descents reaching each
+pub struct LocalOptimaSample {
+    pub local_optima: Vec<Solution>,
+    pub counts: Vec<usize>,
+    pub n_descents: usize,
+}
+
+/// Runs steepest descents from random solutions
+pub fn sample_local_optima(problem: &QapProblem, n_descents: usize) -> LocalOptimaSample {
+    let mut rng = rand::thread_rng();
+    let mut solver = SteepestLSSolver::new(problem);
+    let mut index: HashMap<Vec<usize>, usize> = HashMap::new();
+    let mut sample = LocalOptimaSample {
+        local_optima: vec![],
+        counts: vec![],
+        n_descents,
+    };
+    for _ in 0..n_descents {
+        let local_optimum = solver.solve_steepest(random_solution(&mut rng, problem.get_n()));
+        match index.get(&local_optimum.solution_array) {
+            Some(&i) => sample.counts[i] += 1,
+            None => {
+                index.insert(local_optimum.solution_array.clone(), sample.local_optima.len());
+                sample.local_optima.push(local_optimum);
+                sample.counts.push(1);
+            }
+        }
+    }
+    sample
+}
+
+/// Returns the optimal solution from a .sln file in the convention of
+/// Solution (solution_array[location] = facility). Some QAPLIB solution
+/// files store the inverse permutation, the one matching the objective
+/// value of the file is returned. Returns None if neither matches.
+pub fn optimum_solution(problem: &QapProblem, sln: &Solution) -> Option<Solution> {
+    if sln.solution_array.len() != problem.get_n() {
+        return None;
+    }
+    let mut inverse = vec![0; sln.solution_array.len()];
+    for (location, &facility) in sln.solution_array.iter().enumerate() {
+        inverse[facility] = location;
+    }
+    [sln.solution_array.clone(), inverse]
+        .into_iter()
+        .map(Solution::new)
+        .find_map(|mut solution| {
+            let evaluation = solution.evaluate(problem.matrix_a_ref(), problem.matrix_b_ref());
+            (evaluation == sln.get_eval()).then_some(solution)
+        })
+}
+
+/// Computes the landscape metrics of an instance. The optimum (e.g. from
+/// the .sln file) enables the gap and, if its permutation is consistent
+/// with its evaluation, the distance based metrics.
+pub fn analyse(
+    instance: &str,
+    problem: &QapProblem,
+    optimum: Option<&Solution>,
+    settings: &LandscapeSettings,
+) -> LandscapeMetrics {
+    let walk = random_walk(problem, settings.walk_length);
+    let autocorrelation: Vec<f64> = (1..=settings.max_lag).map(|lag| autocorrelation(&walk, lag)).collect();
+    let correlation_length = correlation_length(autocorrelation.first().copied().unwrap_or(f64::NAN));
+
+    let sample = sample_local_optima(problem, settings.n_descents);
+    let n_descents = settings.n_descents.max(1) as f64;
+    let basin_sizes: Vec<f64> = sample.counts.iter().map(|&c| c as f64 / n_descents).collect();
+    let best = (0..sample.local_optima.len()).min_by_key(|&i| sample.local_optima[i].get_eval());
+
+    // Distances need the optimal permutation, gaps only its evaluation
+    let optimal_solution = optimum.and_then(|sln| optimum_solution(problem, sln));
+    let fitness: Vec<f64> = sample.local_optima.iter().map(|s| s.get_eval() as f64).collect();
+    let distances: Option<Vec<f64>> = optimal_solution.as_ref().map(|optimum| {
+        sample
+            .local_optima
+            .iter()
+            .map(|s| s.hamming_distance(optimum) as f64)
+            .collect()
+    });
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len().max(1) as f64;
+
+    LandscapeMetrics {
+        instance: instance.to_string(),
+        n: problem.get_n(),
+        autocorrelation,
+        correlation_length,
+        n_descents: settings.n_descents,
+        n_local_optima: sample.local_optima.len(),
+        local_optima_density: sample.local_optima.len() as f64 / n_descents,
+        mean_basin_size: mean(&basin_sizes),
+        max_basin_size: basin_sizes.iter().copied().fold(0.0, f64::max),
+        best_basin_size: best.map(|i| basin_sizes[i]).unwrap_or(0.0),
+        mean_local_optimum_gap: optimum.map(|optimum| {
+            let gaps: Vec<f64> = sample
+                .local_optima
+                .iter()
+                .map(|s| relative_gap(s.get_eval(), optimum.get_eval()))
+                .collect();
+            mean(&gaps)
+        }),
+        fitness_distance_correlation: distances.as_ref().map(|d| fitness_distance_correlation(&fitness, d)),
+        mean_distance_to_optimum: distances.as_ref().map(|d| mean(d)),
+    }
+}
+
+/// Saves the metrics of all instances in a csv file according to the provided path
+pub fn save_landscape_metrics(metrics: &[LandscapeMetrics], path: &str) -> Result<(), Box<dyn Error>> {
+    let mut wtr = Writer::from_path(path)?;
+    let max_lag = metrics.iter().map(|m| m.autocorrelation.len()).max().unwrap_or(0);
+    // Write column names
+    let mut header: Vec<String> = ["instance", "n"].iter().map(|s| s.to_string()).collect();
+    header.extend((1..=max_lag).map(|lag| format!("autocorrelation_{}", lag)));
+    header.extend(
+        [
+            "correlation_length", "n_descents", "n_local_optima", "local_optima_density",
+            "mean_basin_size", "max_basin_size", "best_basin_size", "mean_local_optimum_gap",
+            "fitness_distance_correlation", "mean_distance_to_optimum",
+        ]
+        .iter()
+        .map(|s| s.to_string()),
+    );
+    wtr.write_record(&header)?;
+    let format_option = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+    for m in metrics {
+        let mut record = vec![m.instance.clone(), m.n.to_string()];
+        record.extend((0..max_lag).map(|lag| format_option(m.autocorrelation.get(lag).copied())));
+        record.extend([
+            m.correlation_length.to_string(),
+            m.n_descents.to_string(),
+            m.n_local_optima.to_string(),
+            m.local_optima_density.to_string(),
+            m.mean_basin_size.to_string(),
+            m.max_basin_size.to_string(),
+            m.best_basin_size.to_string(),
+            format_option(m.mean_local_optimum_gap),
+            format_option(m.fitness_distance_correlation),
+            format_option(m.mean_distance_to_optimum),
+        ]);
+        wtr.write_record(&record)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
@@ -1,6 +1,22 @@
+pub mod acceptance;
 pub mod candidate_move;
+pub mod catalogue;
+pub mod checkpoint;
+pub mod cooling;
+pub mod diversity;
 pub mod experiment;
+pub mod features;
+pub mod generators;
+pub mod landscape;
+pub mod neighborhood;
+pub mod parallel;
 pub mod qap_problem;
+pub mod report;
+pub mod run_length;
 pub mod solution;
 pub mod solvers;
+pub mod stats;
+pub mod summary;
+pub mod trace;
+pub mod tuning;
 pub mod utils;
@@ -1,3 +1,4 @@
+use qap_local_search::catalogue::{Catalogue, Selection};
 use qap_local_search::qap_problem::QapProblem;
 use qap_local_search::experiment::Experiment;
 use qap_local_search::solvers::tabu_search_solver::TSSolver;
@@ -7,6 +8,7 @@ use qap_local_search::solvers::simulated_annealing_solver::SASolver;
 use qap_local_search::solvers::greedy_ls_solver::GreedyLSSolver;
 use qap_local_search::solvers::steepest_ls_solver::SteepestLSSolver;
 
+use std::env;
 use std::path::Path;
 
 /// Instance file names
@@ -31,9 +33,29 @@ const NRUNS: usize = 10;
 
 
 
+/// Returns the file names of the instances selected by the first
+/// argument (e.g. "tai*a n<=60"), or INSTANCES if there is none
+fn selected_instances() -> Result<Vec<String>, String> {
+    let text = match env::args().nth(1) {
+        Some(text) => text,
+        None => return Ok(INSTANCES.iter().map(|s| s.to_string()).collect()),
+    };
+    let selection = Selection::parse(&text)?;
+    let data_path = Path::new(".").join("data").join(DATA_FOLDER).to_string_lossy().to_string();
+    let catalogue = Catalogue::scan(&data_path).map_err(|err| err.to_string())?;
+    Ok(catalogue.select(&selection).iter().map(|entry| entry.name.clone()).collect())
+}
+
 /// Runs experiments across various solvers (algorithms)
 fn main() {
-    for instance_filename in INSTANCES {
+    let instances = match selected_instances() {
+        Ok(instances) => instances,
+        Err(err) => {
+            eprintln!("Error: {}", err);
+            return;
+        }
+    };
+    for instance_filename in &instances {
         let instance_path = Path::new(".")
             .join("data")
             .join(DATA_FOLDER)
@@ -0,0 +1,340 @@
+use rand::{Rng, RngCore};
+
+use crate::solution::Solution;
+use crate::utils::generate_pairs;
+
+/// A move of a neighbourhood, given by the locations it changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Move {
+    /// Exchanges the facilities of two locations
+    Swap([usize; 2]),
+    /// Rotates the facilities of three locations: the facility of the
+    /// first location moves to the second, the second to the third
+    /// and the third to the first
+    Cycle([usize; 3]),
+    /// Two swaps of four distinct locations
+    DoubleSwap([usize; 2], [usize; 2]),
+}
+
+impl Move {
+    /// Returns the locations changed by the move
+    pub fn locations(&self) -> Vec<usize> {
+        match *self {
+            Move::Swap(pair) => pair.to_vec(),
+            Move::Cycle(triple) => triple.to_vec(),
+            Move::DoubleSwap(p, q) => vec![p[0], p[1], q[0], q[1]],
+        }
+    }
+
+    /// Returns the (location, new facility) assignments of the move,
+    /// the first `len` entries of the array are used
+    fn assignments(&self, solution: &Solution) -> ([(usize, usize); 4], usize) {
+        let s = &solution.solution_array;
+        match *self {
+            Move::Swap([i, j]) => ([(i, s[j]), (j, s[i]), (0, 0), (0, 0)], 2),
+            Move::Cycle([i, j, k]) => ([(j, s[i]), (k, s[j]), (i, s[k]), (0, 0)], 3),
+            Move::DoubleSwap([i, j], [k, l]) => ([(i, s[j]), (j, s[i]), (k, s[l]), (l, s[k])], 4),
+        }
+    }
+
+    /// Returns the change of the evaluation caused by the move.
+    /// Swaps use Solution::calculate_delta, other moves the general
+    /// O(kn) delta of a move changing k locations.
+    pub fn delta(&self, solution: &Solution, matrix_a: &[Vec<i32>], matrix_b: &[Vec<i32>]) -> i32 {
+        match self {
+            Move::Swap(pair) => solution.calculate_delta(matrix_a, matrix_b, pair),
+            _ => {
+                let (assignments, len) = self.assignments(solution);
+                assignment_delta(solution, matrix_a, matrix_b, &assignments[..len])
+            }
+        }
+    }
+
+    /// Applies the move to the solution, the evaluation is not updated
+    pub fn apply(&self, solution: &mut Solution) {
+        match *self {
+            Move::Swap(pair) => solution.exchange_facilities(&pair),
+            Move::Cycle([i, j, k]) => {
+                // Exchanging (i, j) then (i, k) moves i -> j, j -> k, k -> i
+                solution.exchange_facilities(&[i, j]);
+                solution.exchange_facilities(&[i, k]);
+            }
+            Move::DoubleSwap(p, q) => {
+                solution.exchange_facilities(&p);
+                solution.exchange_facilities(&q);
+            }
+        }
+    }
+}
+
+/// Returns the change of the evaluation when the given locations receive
+/// new facilities, in O(kn) for k changed locations. Only the terms of
+/// pairs of locations with at least one changed location differ.
+pub fn assignment_delta(
+    solution: &Solution,
+    matrix_a: &[Vec<i32>],
+    matrix_b: &[Vec<i32>],
+    assignments: &[(usize, usize)],
+) -> i32 {
+    let s = &solution.solution_array;
+    // Terms of the row and column of location i when only i changes
+    let unchanged_terms = |i: usize, fi: usize, j: usize| {
+        matrix_a[i][j] * (matrix_b[fi][s[j]] - matrix_b[s[i]][s[j]])
+            + matrix_a[j][i] * (matrix_b[s[j]][fi] - matrix_b[s[j]][s[i]])
+    };
+    let mut delta = 0;
+    for &(i, fi) in assignments {
+        for j in 0..s.len() {
+            delta += unchanged_terms(i, fi, j);
+        }
+        // Pairs of changed locations are replaced by their exact
+        // terms, each ordered pair counted once
+        for &(j, fj) in assignments {
+            delta -= unchanged_terms(i, fi, j);
+            delta += matrix_a[i][j] * (matrix_b[fi][fj] - matrix_b[s[i]][s[j]]);
+        }
+    }
+    delta
+}
+
+/// A neighbourhood of a permutation, its moves are iterated lazily.
+/// Neighbourhoods are shared by the threads of parallel scans.
+pub trait Neighborhood: Send + Sync {
+    /// Returns the name of the neighbourhood
+    fn name(&self) -> &'static str;
+
+    /// Returns the number of moves of a solution of size n
+    fn size(&self, n: usize) -> usize;
+
+    /// Returns an iterator over all moves of a solution of size n
+    fn moves(&self, n: usize) -> Box<dyn Iterator<Item = Move>>;
+
+    /// Returns a uniformly random move of a solution of size n
+    fn random_move(&self, rng: &mut dyn RngCore, n: usize) -> Move;
+
+    /// Returns the change of the evaluation caused by the move
+    fn delta(&self, solution: &Solution, matrix_a: &[Vec<i32>], matrix_b: &[Vec<i32>], mv: &Move) -> i32 {
+        mv.delta(solution, matrix_a, matrix_b)
+    }
+
+    /// Applies the move and updates the evaluation by the delta
+    fn apply(&self, solution: &mut Solution, mv: &Move, delta: i32) {
+        mv.apply(solution);
+        solution.set_eval(solution.get_eval() + delta);
+    }
+}
+
+/// Returns k distinct random indices below n, panics if n < k
+fn random_distinct<const K: usize>(rng: &mut dyn RngCore, n: usize) -> [usize; K] {
+    assert!(n >= K, "cannot draw {} distinct indices below {}", K, n);
+    let mut indices = [0; K];
+    for i in 0..K {
+        indices[i] = loop {
+            let candidate = rng.gen_range(0..n);
+            if !indices[..i].contains(&candidate) {
+                break candidate;
+            }
+        };
+    }
+    indices
+}
+
+/// Pairwise exchanges of facilities, n(n-1)/2 moves
+pub struct SwapNeighborhood;
+
+impl Neighborhood for SwapNeighborhood {
+    fn name(&self) -> &'static str {
+        "swap"
+    }
+    fn size(&self, n: usize) -> usize {
+        n * n.saturating_sub(1) / 2
+    }
+    fn moves(&self, n: usize) -> Box<dyn Iterator<Item = Move>> {
+        Box::new((0..n).flat_map(move |i| (i + 1..n).map(move |j| Move::Swap([i, j]))))
+    }
+    fn random_move(&self, rng: &mut dyn RngCore, n: usize) -> Move {
+        let [i, j] = random_distinct::<2>(rng, n);
+        Move::Swap([i.min(j), i.max(j)])
+    }
+}
+
+/// Rotations of the facilities of three locations, both directions
+/// of every triple, n(n-1)(n-2)/3 moves
+pub struct CycleNeighborhood;
+
+impl Neighborhood for CycleNeighborhood {
+    fn name(&self) -> &'static str {
+        "3-cycle"
+    }
+    fn size(&self, n: usize) -> usize {
+        n * n.saturating_sub(1) * n.saturating_sub(2) / 3
+    }
+    fn moves(&self, n: usize) -> Box<dyn Iterator<Item = Move>> {
+        Box::new((0..n).flat_map(move |i| {
+            (i + 1..n).flat_map(move |j| {
+                (j + 1..n).flat_map(move |k| [Move::Cycle([i, j, k]), Move::Cycle([i, k, j])])
+            })
+        }))
+    }
+    fn random_move(&self, rng: &mut dyn RngCore, n: usize) -> Move {
+        let [i, j, k] = random_distinct::<3>(rng, n);
+        Move::Cycle([i, j, k])
+    }
+}
+
+/// Two swaps of four distinct locations, every unordered
+/// pair of disjoint swaps once, n(n-1)(n-2)(n-3)/8 moves
+pub struct DoubleSwapNeighborhood;
+
+impl Neighborhood for DoubleSwapNeighborhood {
+    fn name(&self) -> &'static str {
+        "double-swap"
+    }
+    fn size(&self, n: usize) -> usize {
+        n * n.saturating_sub(1) * n.saturating_sub(2) * n.saturating_sub(3) / 8
+    }
+    fn moves(&self, n: usize) -> Box<dyn Iterator<Item = Move>> {
+        // The first swap has the smallest location
+        Box::new((0..n).flat_map(move |i| {
+            (i + 1..n).flat_map(move |j| {
+                (i + 1..n).filter(move |&k| k != j).flat_map(move |k| {
+                    (k + 1..n)
+                        .filter(move |&l| l != j)
+                        .map(move |l| Move::DoubleSwap([i, j], [k, l]))
+                })
+            })
+        }))
+    }
+    fn random_move(&self, rng: &mut dyn RngCore, n: usize) -> Move {
+        let [i, j, k, l] = random_distinct::<4>(rng, n);
+        Move::DoubleSwap([i, j], [k, l])
+    }
+}
+
+/// Samples the swaps of simulated annealing and the acceptance-based
+/// searches: the swaps are visited cyclically in a fixed order,
+/// starting from a random position
+pub struct SwapSampler {
+    moves: Vec<[usize; 2]>,
+    position: usize,
+}
+
+impl SwapSampler {
+    // Constructor
+    pub fn new(n: usize) -> SwapSampler {
+        SwapSampler {
+            // Vector of pairs (i, j), moves in order
+            moves: generate_pairs(n),
+            position: 0,
+        }
+    }
+
+    /// Returns the number of swaps
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Returns true if there are no swaps (n < 2)
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Moves to a random position
+    pub fn restart<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        if self.moves.is_empty() {
+            self.position = 0;
+            return;
+        }
+        self.position = rng.gen_range(0..self.moves.len());
+    }
+
+    /// Returns the index of the next swap
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Sets the index of the next swap, returns false if out of range
+    pub fn set_position(&mut self, position: usize) -> bool {
+        let valid = position < self.moves.len();
+        if valid {
+            self.position = position;
+        }
+        valid
+    }
+
+    /// Returns a uniformly random swap, the position does not change.
+    /// Returns None if there are no swaps
+    pub fn random_move<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<[usize; 2]> {
+        if self.moves.is_empty() {
+            return None;
+        }
+        Some(self.moves[rng.gen_range(0..self.moves.len())])
+    }
+
+    /// Returns the next swap with its delta and advances the position
+    pub fn next_move(&mut self, solution: &Solution, matrix_a: &[Vec<i32>], matrix_b: &[Vec<i32>]) -> ([usize; 2], i32) {
+        let pair = self.moves[self.position];
+        self.position = (self.position + 1) % self.moves.len();
+        (pair, solution.calculate_delta(matrix_a, matrix_b, &pair))
+    }
+}
+
+/// Names of the neighbourhoods that can be built by `build_neighborhood`
+pub const NEIGHBORHOOD_NAMES: [&str; 3] = ["swap", "3-cycle", "double-swap"];
+
+/// Builds a neighbourhood by name
+pub fn build_neighborhood(name: &str) -> Option<Box<dyn Neighborhood>> {
+    match name {
+        "swap" => Some(Box::new(SwapNeighborhood)),
+        "3-cycle" => Some(Box::new(CycleNeighborhood)),
+        "double-swap" => Some(Box::new(DoubleSwapNeighborhood)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::qap_problem::QapProblem;
+    use crate::utils::{arange, permute_array};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn deltas_match_full_evaluation() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for instance in ["nug12.dat", "bur26a.dat", "esc16c.dat"] {
+            let problem = QapProblem::new(&format!("data/qapdatsol/{}", instance)).unwrap();
+            let (matrix_a, matrix_b) = (problem.matrix_a_ref(), problem.matrix_b_ref());
+            let n = problem.get_n();
+            for name in NEIGHBORHOOD_NAMES {
+                let neighborhood = build_neighborhood(name).unwrap();
+                for _ in 0..20 {
+                    let mut solution_array = vec![0; n];
+                    arange(&mut solution_array, 0, 1);
+                    permute_array(&mut rng, &mut solution_array);
+                    let mut solution = Solution::new(solution_array);
+                    solution.evaluate(matrix_a, matrix_b);
+                    for _ in 0..20 {
+                        let mv = neighborhood.random_move(&mut rng, n);
+                        let delta = neighborhood.delta(&solution, matrix_a, matrix_b, &mv);
+                        neighborhood.apply(&mut solution, &mv, delta);
+                        let incremental = solution.get_eval();
+                        solution.evaluate(matrix_a, matrix_b);
+                        assert_eq!(incremental, solution.get_eval(), "{} {} {:?}", instance, name, mv);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sampler_without_swaps() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut sampler = SwapSampler::new(1);
+        sampler.restart(&mut rng);
+        assert!(sampler.is_empty());
+        assert_eq!(sampler.position(), 0);
+        assert_eq!(sampler.random_move(&mut rng), None);
+    }
+}
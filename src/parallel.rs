@@ -0,0 +1,94 @@
+use std::thread;
+
+/// Returns the bounds of `n_threads` contiguous chunks of `len` items,
+/// the first chunks are one item longer if `len` is not divisible
+pub fn chunk_bounds(len: usize, n_threads: usize) -> Vec<(usize, usize)> {
+    let n_threads = n_threads.clamp(1, len.max(1));
+    let (size, remainder) = (len / n_threads, len % n_threads);
+    let mut bounds = Vec::with_capacity(n_threads);
+    let mut start = 0;
+    for t in 0..n_threads {
+        let end = start + size + usize::from(t < remainder);
+        bounds.push((start, end));
+        start = end;
+    }
+    bounds
+}
+
+/// Maps the items with `n_threads` worker threads, each mapping a
+/// contiguous chunk. The results are in the order of the items, so
+/// they do not depend on the number of threads. One thread maps the
+/// items without spawning.
+pub fn parallel_map<T, R, F>(items: &[T], n_threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if n_threads <= 1 || items.len() < 2 {
+        return items.iter().map(f).collect();
+    }
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = chunk_bounds(items.len(), n_threads)
+            .into_iter()
+            .map(|(start, end)| scope.spawn(move || items[start..end].iter().map(f).collect::<Vec<R>>()))
+            .collect();
+        handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+    })
+}
+
+/// Applies `f` to every item with `n_threads` worker threads, each
+/// handling a contiguous chunk. One thread applies it without spawning.
+pub fn parallel_for_each<T, F>(items: &mut [T], n_threads: usize, f: F)
+where
+    T: Send,
+    F: Fn(&mut T) + Sync,
+{
+    if n_threads <= 1 || items.len() < 2 {
+        items.iter_mut().for_each(f);
+        return;
+    }
+    let f = &f;
+    let bounds = chunk_bounds(items.len(), n_threads);
+    thread::scope(|scope| {
+        let mut rest = items;
+        for (start, end) in bounds {
+            let (chunk, tail) = rest.split_at_mut(end - start);
+            rest = tail;
+            scope.spawn(move || chunk.iter_mut().for_each(f));
+        }
+    });
+}
+
+/// Returns the index and the key of the smallest key of `len` indexed
+/// items, evaluated by `key` over contiguous index ranges (start, end)
+/// by `n_threads` worker threads. `key` returns the smallest key of its
+/// range with the index, and ties are broken by the smallest index,
+/// so the result does not depend on the number of threads.
+pub fn parallel_min<K, F>(len: usize, n_threads: usize, key: F) -> Option<(usize, K)>
+where
+    K: Ord + Send,
+    F: Fn(usize, usize) -> Option<(usize, K)> + Sync,
+{
+    if n_threads <= 1 || len < 2 {
+        return key(0, len);
+    }
+    let key = &key;
+    let minima: Vec<Option<(usize, K)>> = thread::scope(|scope| {
+        let handles: Vec<_> = chunk_bounds(len, n_threads)
+            .into_iter()
+            .map(|(start, end)| scope.spawn(move || key(start, end)))
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+    minima
+        .into_iter()
+        .flatten()
+        .min_by(|(i, a), (j, b)| a.cmp(b).then(i.cmp(j)))
+}
+
+/// Returns the number of threads available to the process, 1 if unknown
+pub fn available_threads() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
@@ -1,8 +1,9 @@
+use crate::features::InstanceFeatures;
 use crate::solution::Solution;
 use crate::utils::*;
 use rand::rngs::ThreadRng;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs::{self, File};
+use std::io::{BufWriter, Error, ErrorKind, Write};
 
 pub struct QapProblem {
     n: usize,
@@ -26,6 +27,41 @@ impl QapProblem {
         }
     }
 
+    /// Constructor from matrices A (distances) and B (flows),
+    /// both must be square and of the same size
+    pub fn from_matrices(matrix_a: Vec<Vec<i32>>, matrix_b: Vec<Vec<i32>>) -> Result<QapProblem, Error> {
+        let n = matrix_a.len();
+        let is_square = |matrix: &Vec<Vec<i32>>| matrix.len() == n && matrix.iter().all(|row| row.len() == n);
+        if !is_square(&matrix_a) || !is_square(&matrix_b) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "matrices must be square and of the same size",
+            ));
+        }
+        Ok(QapProblem {
+            n,
+            matrix_a,
+            matrix_b,
+            rng: rand::thread_rng(),
+        })
+    }
+
+    /// Writes the instance in the QAPLIB format: the size followed
+    /// by matrices A and B, separated by empty lines
+    pub fn write_qaplib(&self, filename: &str) -> Result<(), Error> {
+        let mut file = BufWriter::new(File::create(filename)?);
+        writeln!(file, "{}", self.n)?;
+        for matrix in [&self.matrix_a, &self.matrix_b] {
+            writeln!(file)?;
+            let width = matrix.iter().flatten().map(|v| v.to_string().len()).max().unwrap_or(1);
+            for row in matrix {
+                let row: Vec<String> = row.iter().map(|v| format!("{:>width$}", v, width = width)).collect();
+                writeln!(file, "{}", row.join(" "))?;
+            }
+        }
+        file.flush()
+    }
+
     // TODO: this function can't be used for now as it requires
     // &mut reference. QAPProblem object is not mutable when passed
     // to the solvers.
@@ -50,57 +86,39 @@ impl QapProblem {
         self.n
     }
 
-    /// Function to parse the file describing the instance
-    fn parse_file(filename: &str) -> Result<(usize, Vec<Vec<i32>>, Vec<Vec<i32>>), std::io::Error> {
-        let file = File::open(filename)?;
-        let reader = BufReader::new(file);
-        let mut lines = reader.lines();
-
-        // Parse the first line to get the size of the matrices
-        let n: usize = lines.next().unwrap()?.trim().parse().unwrap();
+    /// Computes descriptive features of the instance
+    pub fn features(&self) -> InstanceFeatures {
+        InstanceFeatures::new(self)
+    }
 
-        // Parse matrix A
-        let mut matrix_a = Vec::with_capacity(n);
-        let mut i = 0;
-        while i < n {
-            let line = lines.next().unwrap()?;
-            // Handle empty lines
-            if line.len() != 0 {
-                let row: Vec<i32> = line
-                    .split_whitespace()
-                    .map(|s| s.parse().unwrap())
-                    .collect();
-                matrix_a.push(row);
-                i += 1;
-            }
-        }
+    /// Function to parse the file describing the instance. The file
+    /// stores the size followed by matrices A and B, rows may wrap
+    /// across lines and blank lines are ignored.
+    fn parse_file(filename: &str) -> Result<(usize, Vec<Vec<i32>>, Vec<Vec<i32>>), Error> {
+        let content = fs::read_to_string(filename)?;
+        let invalid = |msg: &str| Error::new(ErrorKind::InvalidData, format!("{}: {}", filename, msg));
+        let mut tokens = content.split_whitespace();
 
-        // Parse matrix B
-        let mut matrix_b: Vec<Vec<i32>> = Vec::with_capacity(n);
-        for _ in 0..n {
-            let mut row: Vec<i32> = Vec::with_capacity(n);
-            let mut count: usize = 0;
-            let mut line: String = String::new();
-            loop {
-                line += &lines.next().unwrap()?;
-                let nums: Vec<&str> = line.split_whitespace().collect();
-                for num in nums {
-                    if count < n {
-                        row.push(num.parse().unwrap());
-                        count += 1;
-                    } else {
-                        break;
+        let n: usize = match tokens.next() {
+            Some(token) => token.parse().map_err(|_| invalid("invalid instance size"))?,
+            None => return Err(invalid("empty file")),
+        };
+        let mut read_matrix = || -> Result<Vec<Vec<i32>>, Error> {
+            let mut matrix = Vec::with_capacity(n);
+            for _ in 0..n {
+                let mut row = Vec::with_capacity(n);
+                for _ in 0..n {
+                    match tokens.next() {
+                        Some(token) => row.push(token.parse().map_err(|_| invalid("not a number"))?),
+                        None => return Err(invalid("unexpected end of file")),
                     }
                 }
-                if count >= n {
-                    break;
-                }
+                matrix.push(row);
             }
-            assert_eq!(row.len(), n);
-            matrix_b.push(row);
-        }
-        assert_eq!(matrix_a.len(), n);
-        assert_eq!(matrix_b.len(), n);
+            Ok(matrix)
+        };
+        let matrix_a = read_matrix()?;
+        let matrix_b = read_matrix()?;
         Ok((n, matrix_a, matrix_b))
     }
 }
@@ -0,0 +1,184 @@
+use crate::experiment::ExperimentResults;
+use crate::report::svg::{color, Axis, Chart};
+use crate::summary::relative_gap;
+
+use meansd::MeanSD;
+
+/// Results of one solver on an instance, as drawn on the charts
+pub type SolverResults<'a> = (&'a str, &'a ExperimentResults);
+
+/// Returns the quality of an evaluation: the gap (%) to the optimum
+/// if it is known, otherwise the evaluation itself
+fn quality(evaluation: i32, optimum: Option<i32>) -> f64 {
+    match optimum {
+        Some(optimum) => relative_gap(evaluation, optimum),
+        None => evaluation as f64,
+    }
+}
+
+/// Returns the label of the quality axis
+fn quality_label(optimum: Option<i32>) -> &'static str {
+    match optimum {
+        Some(_) => "Gap to the optimum (%)",
+        None => "Evaluation",
+    }
+}
+
+/// Box plots of the final gaps (or evaluations) of every solver on an instance
+pub fn gap_box_plot(instance: &str, optimum: Option<i32>, solvers: &[SolverResults]) -> String {
+    let qualities: Vec<Vec<f64>> = solvers
+        .iter()
+        .map(|(_, results)| {
+            results
+                .final_evaluations()
+                .iter()
+                .map(|&evaluation| quality(evaluation, optimum))
+                .collect()
+        })
+        .collect();
+    let all: Vec<f64> = qualities.iter().flatten().copied().collect();
+    let categories = solvers.iter().map(|(solver, _)| solver.to_string()).collect();
+    let mut chart = Chart::with_categories(
+        &format!("Final solutions on {}", instance),
+        "Solver",
+        categories,
+        Axis::fit(quality_label(optimum), &all),
+    );
+    for (i, values) in qualities.iter().enumerate() {
+        chart.box_plot(i as f64, values, color(i));
+    }
+    chart.render()
+}
+
+/// Best-so-far convergence curves of every run (thin lines) and the
+/// median curve of every solver (thick line) over logarithmic time.
+/// Needs recorded traces, runs without one are skipped.
+pub fn convergence_plot(instance: &str, optimum: Option<i32>, solvers: &[SolverResults]) -> String {
+    let mut times = vec![];
+    let mut values = vec![];
+    for (_, results) in solvers {
+        for point in results.traces.iter().flatten() {
+            times.push(point.time);
+            values.push(quality(point.evaluation, optimum));
+        }
+        times.extend(results.elapsed_time.iter().map(|&t| t as f64));
+    }
+    let end_time = times.iter().copied().fold(0.0, f64::max);
+    let mut chart = Chart::new(
+        &format!("Convergence on {}", instance),
+        Axis::fit_log("Time (ms)", &times),
+        Axis::fit(quality_label(optimum), &values),
+    );
+    for (i, (solver, results)) in solvers.iter().enumerate() {
+        let curves: Vec<Vec<(f64, f64)>> = results
+            .traces
+            .iter()
+            .filter(|trace| !trace.is_empty())
+            .map(|trace| {
+                trace
+                    .iter()
+                    .map(|point| (point.time, quality(point.evaluation, optimum)))
+                    .collect()
+            })
+            .collect();
+        for curve in &curves {
+            chart.step_line(curve, end_time, color(i), 1.0, 0.25);
+        }
+        let median = median_curve(&curves);
+        chart.step_line(&median, end_time, color(i), 2.5, 1.0);
+        chart.add_legend(solver, color(i));
+    }
+    if optimum.is_some() {
+        chart.horizontal_line(0.0, "black");
+    }
+    chart.render()
+}
+
+/// Returns the median of best-so-far step curves at the times
+/// of all their points. Only times at which every curve has
+/// started are included.
+fn median_curve(curves: &[Vec<(f64, f64)>]) -> Vec<(f64, f64)> {
+    let start = curves.iter().filter_map(|c| c.first()).map(|p| p.0).fold(0.0, f64::max);
+    let mut times: Vec<f64> = curves.iter().flatten().map(|p| p.0).filter(|&t| t >= start).collect();
+    times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    times.dedup();
+    times
+        .into_iter()
+        .map(|time| {
+            let mut values: Vec<f64> = curves
+                .iter()
+                .filter_map(|curve| curve.iter().take_while(|p| p.0 <= time).last().map(|p| p.1))
+                .collect();
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            (time, crate::summary::quantile(&values, 0.5))
+        })
+        .collect()
+}
+
+/// Scatter plot of the initial versus final evaluation of every run.
+/// Runs without an initial solution (e.g. random search) are skipped.
+pub fn initial_final_scatter(instance: &str, solvers: &[SolverResults]) -> String {
+    let series: Vec<Vec<(f64, f64)>> = solvers
+        .iter()
+        .map(|(_, results)| {
+            results
+                .initial_solutions
+                .iter()
+                .zip(&results.final_solutions)
+                .filter_map(|(initial, last)| {
+                    initial.as_ref().map(|initial| (initial.get_eval() as f64, last.get_eval() as f64))
+                })
+                .collect()
+        })
+        .collect();
+    let xs: Vec<f64> = series.iter().flatten().map(|p| p.0).collect();
+    let ys: Vec<f64> = series.iter().flatten().map(|p| p.1).collect();
+    let mut chart = Chart::new(
+        &format!("Initial vs final solutions on {}", instance),
+        Axis::fit("Initial evaluation", &xs),
+        Axis::fit("Final evaluation", &ys),
+    );
+    for (i, ((solver, _), points)) in solvers.iter().zip(&series).enumerate() {
+        if points.is_empty() {
+            continue;
+        }
+        chart.points(points, color(i), 3.5, 0.7);
+        chart.add_legend(solver, color(i));
+    }
+    chart.render()
+}
+
+/// Time versus quality of every run (small markers) and the mean
+/// of every solver (large markers), with logarithmic time
+pub fn time_quality_plot(instance: &str, optimum: Option<i32>, solvers: &[SolverResults]) -> String {
+    let series: Vec<Vec<(f64, f64)>> = solvers
+        .iter()
+        .map(|(_, results)| {
+            results
+                .elapsed_time
+                .iter()
+                .zip(results.final_evaluations())
+                // Runs faster than 1 ms are drawn at the axis minimum
+                .map(|(&time, evaluation)| ((time as f64).max(0.1), quality(evaluation, optimum)))
+                .collect()
+        })
+        .collect();
+    let xs: Vec<f64> = series.iter().flatten().map(|p| p.0).collect();
+    let ys: Vec<f64> = series.iter().flatten().map(|p| p.1).collect();
+    let mut chart = Chart::new(
+        &format!("Time vs quality on {}", instance),
+        Axis::fit_log("Time (ms)", &xs),
+        Axis::fit(quality_label(optimum), &ys),
+    );
+    for (i, ((solver, _), points)) in solvers.iter().zip(&series).enumerate() {
+        chart.points(points, color(i), 2.5, 0.4);
+        let (mut time, mut value) = (MeanSD::default(), MeanSD::default());
+        for &(x, y) in points {
+            time.update(x);
+            value.update(y);
+        }
+        chart.points(&[(time.mean(), value.mean())], color(i), 7.0, 1.0);
+        chart.add_legend(solver, color(i));
+    }
+    chart.render()
+}
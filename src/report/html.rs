@@ -0,0 +1,176 @@
+use std::fmt::Write;
+
+use crate::report::svg::escape;
+use crate::report::{instance_charts, InstanceResults, TRACES_FOLDER};
+use crate::stats::{friedman_nemenyi, pairwise_tests, PairwiseComparison, ResultSet};
+use crate::summary::{summaries_to_html, RunSummary};
+
+/// Settings of the HTML report
+#[derive(Debug, Clone)]
+pub struct HtmlReportSettings {
+    pub title: String,
+    /// The gap (%) at which a run counts as successful
+    pub target_gap: f64,
+    /// Significance level of the statistical tests
+    pub alpha: f64,
+    /// Path of the results folder relative to the report, used by the links
+    pub results_link: String,
+}
+
+impl Default for HtmlReportSettings {
+    fn default() -> Self {
+        HtmlReportSettings {
+            title: "Experiment report".to_string(),
+            target_gap: 1.0,
+            alpha: 0.05,
+            results_link: ".".to_string(),
+        }
+    }
+}
+
+/// Style sheet of the report
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em auto; max-width: 1100px; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #cccccc; padding: 3px 8px; text-align: right; }
+th { background: #f0f0f0; }
+td:first-child, td:nth-child(2) { text-align: left; }
+.significant { font-weight: bold; }
+.charts svg { max-width: 100%; height: auto; }";
+
+/// Returns a table of pairwise comparisons
+fn comparisons_table(comparisons: &[&PairwiseComparison]) -> String {
+    let mut table = String::from(
+        "<table>\n<tr><th>Solver A</th><th>Solver B</th><th>Test</th><th>Statistic</th><th>p-value</th><th>Adjusted p-value</th></tr>\n",
+    );
+    for comparison in comparisons {
+        let class = if comparison.significant { " class=\"significant\"" } else { "" };
+        let _ = writeln!(
+            table,
+            "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{:.3}</td><td>{:.4}</td><td>{:.4}</td></tr>",
+            class,
+            escape(&comparison.solver_a),
+            escape(&comparison.solver_b),
+            comparison.test,
+            comparison.statistic,
+            comparison.p_value,
+            comparison.adjusted_p_value
+        );
+    }
+    table += "</table>\n";
+    table
+}
+
+/// Renders a self-contained HTML report of a campaign: metadata, summary
+/// table, pairwise tests and charts of every instance, followed by the
+/// Friedman test with the Nemenyi post-hoc over all instances
+pub fn render_html_report(instances: &[InstanceResults], settings: &HtmlReportSettings) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n<h1>{title}</h1>\n",
+        title = escape(&settings.title)
+    );
+    let _ = writeln!(
+        html,
+        "<p>Runs succeed when they are within {}% of the optimum. Comparisons in bold are significant at level {}.</p>",
+        settings.target_gap, settings.alpha
+    );
+
+    // Table of contents
+    html += "<ul>\n";
+    for instance in instances {
+        let _ = writeln!(html, "<li><a href=\"#{0}\">{0}</a></li>", escape(&instance.instance));
+    }
+    html += "<li><a href=\"#ranking\">Ranking over instances</a></li>\n</ul>\n";
+
+    let mut result_sets = vec![];
+    for instance in instances {
+        let name = &instance.instance;
+        let _ = writeln!(html, "<h2 id=\"{0}\">{0}</h2>", escape(name));
+        let optimum = instance.optimum.map(|o| o.to_string()).unwrap_or_else(|| "unknown".to_string());
+        let _ = writeln!(
+            html,
+            "<p>Size n = {}, optimal (best known) evaluation {}.</p>",
+            instance.get_n(),
+            optimum
+        );
+
+        let summaries: Vec<RunSummary> = instance
+            .solvers
+            .iter()
+            .map(|(solver, results)| RunSummary::new(name, solver, results, instance.optimum, settings.target_gap))
+            .collect();
+        html += &summaries_to_html(&summaries);
+
+        // Links to the raw data
+        html += "<p>Raw results:";
+        for (solver, results) in &instance.solvers {
+            let file_name = format!("{}_{}.csv", name, solver);
+            let _ = write!(
+                html,
+                " <a href=\"{}/{}\">{}</a>",
+                settings.results_link,
+                escape(&file_name),
+                escape(solver)
+            );
+            if results.traces.iter().any(|trace| !trace.is_empty()) {
+                let _ = write!(
+                    html,
+                    " (<a href=\"{}/{}/{}\">traces</a>)",
+                    settings.results_link,
+                    TRACES_FOLDER,
+                    escape(&file_name)
+                );
+            }
+        }
+        html += "</p>\n";
+
+        let instance_sets: Vec<ResultSet> = instance
+            .solvers
+            .iter()
+            .map(|(solver, results)| ResultSet::new(name, solver, results))
+            .collect();
+        if instance_sets.len() > 1 {
+            let comparisons = pairwise_tests(&instance_sets, settings.alpha);
+            html += "<h3>Pairwise tests</h3>\n";
+            html += &comparisons_table(&comparisons.iter().collect::<Vec<_>>());
+        }
+        result_sets.extend(instance_sets);
+
+        html += "<div class=\"charts\">\n";
+        for (_, svg) in instance_charts(instance) {
+            html += &svg;
+        }
+        html += "</div>\n";
+    }
+
+    html += "<h2 id=\"ranking\">Ranking over instances</h2>\n";
+    let friedman = friedman_nemenyi(&result_sets, settings.alpha);
+    if friedman.solvers.len() > 1 && friedman.instances.len() > 1 {
+        let _ = writeln!(
+            html,
+            "<p>Friedman test over {} instances solved by all solvers: statistic {:.3}, p-value {:.4}. Nemenyi critical difference {:.3}.</p>",
+            friedman.instances.len(),
+            friedman.test.statistic,
+            friedman.test.p_value,
+            friedman.critical_difference
+        );
+        let mut order: Vec<usize> = (0..friedman.solvers.len()).collect();
+        order.sort_by(|&a, &b| friedman.average_ranks[a].partial_cmp(&friedman.average_ranks[b]).unwrap());
+        html += "<table>\n<tr><th>Solver</th><th>Average rank</th></tr>\n";
+        for i in order {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{:.2}</td></tr>",
+                escape(&friedman.solvers[i]),
+                friedman.average_ranks[i]
+            );
+        }
+        html += "</table>\n<h3>Nemenyi post-hoc test</h3>\n";
+        html += &comparisons_table(&friedman.post_hoc.iter().collect::<Vec<_>>());
+    } else {
+        html += "<p>The ranking needs at least two solvers run on at least two common instances.</p>\n";
+    }
+    html += "</body>\n</html>\n";
+    html
+}
@@ -0,0 +1,107 @@
+pub mod charts;
+pub mod html;
+pub mod svg;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::experiment::ExperimentResults;
+use crate::solution::Solution;
+
+/// Name of the subfolder of a results folder with the traces
+pub const TRACES_FOLDER: &str = "traces";
+
+/// Results of all solvers on one instance
+pub struct InstanceResults {
+    pub instance: String,
+    /// Evaluation of the optimal (best known) solution, if known
+    pub optimum: Option<i32>,
+    /// Results of every solver, sorted by solver name
+    pub solvers: Vec<(String, ExperimentResults)>,
+}
+
+impl InstanceResults {
+    /// Returns the instance size
+    pub fn get_n(&self) -> usize {
+        self.solvers
+            .iter()
+            .find_map(|(_, results)| results.final_solutions.first())
+            .map(|solution| solution.solution_array.len())
+            .unwrap_or(0)
+    }
+
+    /// Returns the solver results in the form drawn on the charts
+    pub fn solver_results(&self) -> Vec<charts::SolverResults<'_>> {
+        self.solvers.iter().map(|(solver, results)| (solver.as_str(), results)).collect()
+    }
+}
+
+/// Reads all results saved by Experiment::save_results in a folder.
+/// Files are named `<instance>.dat_<solver>.csv`, as in the notebooks,
+/// other csv files are ignored. Traces saved by Experiment::save_traces
+/// are read from the traces subfolder under the same name, and the
+/// optimum from the .sln file of the instance in the data folder.
+/// Instances are sorted by size.
+pub fn read_results_folder(folder: &str, data_folder: &str) -> Result<Vec<InstanceResults>, Box<dyn Error>> {
+    let mut instances: BTreeMap<String, Vec<(String, ExperimentResults)>> = BTreeMap::new();
+    for entry in fs::read_dir(folder)? {
+        let path = entry?.path();
+        let file_name = path.file_name().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let (instance, solver) = match file_name.strip_suffix(".csv").and_then(|name| name.split_once(".dat_")) {
+            Some((instance, solver)) => (instance.to_string() + ".dat", solver.to_string()),
+            None => continue,
+        };
+        let (_, mut results) = match ExperimentResults::read_csv(&path.to_string_lossy()) {
+            Ok(results) => results,
+            Err(err) => {
+                eprintln!("Skipping {}: {}", file_name, err);
+                continue;
+            }
+        };
+        let traces_path = Path::new(folder).join(TRACES_FOLDER).join(&file_name);
+        if traces_path.exists() {
+            results.read_traces_csv(&traces_path.to_string_lossy())?;
+        }
+        instances.entry(instance).or_default().push((solver, results));
+    }
+    let mut instances: Vec<InstanceResults> = instances
+        .into_iter()
+        .map(|(instance, mut solvers)| {
+            solvers.sort_by(|a, b| a.0.cmp(&b.0));
+            let sln_path = Path::new(data_folder).join(&instance).with_extension("sln");
+            let optimum = Solution::read_sln(&sln_path.to_string_lossy()).ok().map(|s| s.get_eval());
+            InstanceResults {
+                instance,
+                optimum,
+                solvers,
+            }
+        })
+        .collect();
+    instances.sort_by_key(|instance| instance.get_n());
+    Ok(instances)
+}
+
+/// Renders all charts of an instance, returns (chart name, SVG document)
+/// pairs. The convergence plot is included only if traces were recorded.
+pub fn instance_charts(instance: &InstanceResults) -> Vec<(String, String)> {
+    let solvers = instance.solver_results();
+    let name = &instance.instance;
+    let mut rendered = vec![
+        ("gaps".to_string(), charts::gap_box_plot(name, instance.optimum, &solvers)),
+        ("initial_final".to_string(), charts::initial_final_scatter(name, &solvers)),
+        ("time_quality".to_string(), charts::time_quality_plot(name, instance.optimum, &solvers)),
+    ];
+    let has_traces = solvers.iter().any(|(_, results)| results.traces.iter().any(|t| !t.is_empty()));
+    if has_traces {
+        rendered.push(("convergence".to_string(), charts::convergence_plot(name, instance.optimum, &solvers)));
+    }
+    rendered
+}
+
+/// Saves an SVG document according to the provided path
+pub fn save_svg(svg: &str, path: &str) -> Result<(), io::Error> {
+    fs::write(path, svg)
+}
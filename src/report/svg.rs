@@ -0,0 +1,337 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counter making the ids of charts unique within an HTML page
+static CHART_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Colors of the matplotlib tab10 palette, used by the notebooks
+pub const PALETTE: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd",
+    "#8c564b", "#e377c2", "#7f7f7f", "#bcbd22", "#17becf",
+];
+
+/// Returns the palette color of the i-th series
+pub fn color(i: usize) -> &'static str {
+    PALETTE[i % PALETTE.len()]
+}
+
+/// Escapes text for use in SVG (XML)
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Formats a tick label without trailing zeros
+fn format_tick(value: f64) -> String {
+    if value.abs() < 1e-12 {
+        return "0".to_string();
+    }
+    if value.abs() >= 1e6 || value.abs() < 1e-3 {
+        return format!("{:.0e}", value);
+    }
+    let text = format!("{:.3}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// An axis of a chart, linear or logarithmic
+#[derive(Debug, Clone)]
+pub struct Axis {
+    pub label: String,
+    pub min: f64,
+    pub max: f64,
+    pub log: bool,
+}
+
+impl Axis {
+    /// Constructor of a linear axis covering the values with a margin
+    pub fn fit(label: &str, values: &[f64]) -> Axis {
+        let finite = values.iter().copied().filter(|v| v.is_finite());
+        let (mut min, mut max) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+            (min.min(v), max.max(v))
+        });
+        if min > max {
+            (min, max) = (0.0, 1.0);
+        }
+        let margin = if max > min { 0.05 * (max - min) } else { 0.5 * min.abs().max(1.0) };
+        Axis {
+            label: label.to_string(),
+            min: min - margin,
+            max: max + margin,
+            log: false,
+        }
+    }
+
+    /// Constructor of a logarithmic axis covering the positive values
+    pub fn fit_log(label: &str, values: &[f64]) -> Axis {
+        let positive: Vec<f64> = values.iter().copied().filter(|v| v.is_finite() && *v > 0.0).collect();
+        let min = positive.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = positive.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let (min, max) = if min > max { (1.0, 10.0) } else { (min, max) };
+        Axis {
+            label: label.to_string(),
+            min: 10f64.powf(min.log10().floor()),
+            max: 10f64.powf(max.log10().ceil().max(min.log10().floor() + 1.0)),
+            log: true,
+        }
+    }
+
+    /// Returns the position of a value on the axis, from 0 to 1
+    fn fraction(&self, value: f64) -> f64 {
+        if self.log {
+            let value = value.max(self.min);
+            (value.log10() - self.min.log10()) / (self.max.log10() - self.min.log10())
+        } else {
+            (value - self.min) / (self.max - self.min)
+        }
+    }
+
+    /// Returns the values of the ticks: powers of 10 on a logarithmic
+    /// axis, otherwise multiples of 1, 2 or 5 times a power of 10
+    fn ticks(&self) -> Vec<f64> {
+        if self.log {
+            let first = self.min.log10().round() as i32;
+            let last = self.max.log10().round() as i32;
+            return (first..=last).map(|e| 10f64.powi(e)).collect();
+        }
+        let raw_step = (self.max - self.min) / 6.0;
+        let magnitude = 10f64.powf(raw_step.log10().floor());
+        let step = [1.0, 2.0, 5.0, 10.0]
+            .iter()
+            .map(|m| m * magnitude)
+            .find(|&s| s >= raw_step)
+            .unwrap_or(10.0 * magnitude);
+        let first = (self.min / step).ceil() as i64;
+        let last = (self.max / step).floor() as i64;
+        (first..=last).map(|i| i as f64 * step).collect()
+    }
+}
+
+/// A chart with two axes rendered as a standalone SVG document.
+/// Elements are drawn in data coordinates.
+pub struct Chart {
+    title: String,
+    width: f64,
+    height: f64,
+    x: Axis,
+    y: Axis,
+    /// Names of categories shown on the x axis instead of numeric ticks,
+    /// category i is centered at x = i
+    categories: Vec<String>,
+    body: String,
+    legend: Vec<(String, String)>,
+}
+
+/// Margins of the plot area (left, right, top, bottom)
+const MARGINS: (f64, f64, f64, f64) = (80.0, 150.0, 40.0, 60.0);
+
+impl Chart {
+    /// Constructor
+    pub fn new(title: &str, x: Axis, y: Axis) -> Chart {
+        Chart {
+            title: title.to_string(),
+            width: 720.0,
+            height: 440.0,
+            x,
+            y,
+            categories: vec![],
+            body: String::new(),
+            legend: vec![],
+        }
+    }
+
+    /// Constructor of a chart with categories on the x axis
+    pub fn with_categories(title: &str, x_label: &str, categories: Vec<String>, y: Axis) -> Chart {
+        let x = Axis {
+            label: x_label.to_string(),
+            min: -0.5,
+            max: categories.len() as f64 - 0.5,
+            log: false,
+        };
+        let mut chart = Chart::new(title, x, y);
+        chart.categories = categories;
+        chart
+    }
+
+    /// Returns the horizontal pixel position of a value
+    fn px(&self, x: f64) -> f64 {
+        MARGINS.0 + self.x.fraction(x) * (self.width - MARGINS.0 - MARGINS.1)
+    }
+
+    /// Returns the vertical pixel position of a value
+    fn py(&self, y: f64) -> f64 {
+        self.height - MARGINS.3 - self.y.fraction(y) * (self.height - MARGINS.2 - MARGINS.3)
+    }
+
+    /// Returns the pixel width of a horizontal distance in data units
+    fn pixel_width(&self, dx: f64) -> f64 {
+        self.px(dx) - self.px(0.0)
+    }
+
+    /// Adds an entry to the legend
+    pub fn add_legend(&mut self, label: &str, color: &str) {
+        self.legend.push((label.to_string(), color.to_string()));
+    }
+
+    /// Draws circle markers
+    pub fn points(&mut self, points: &[(f64, f64)], color: &str, radius: f64, opacity: f64) {
+        for &(x, y) in points {
+            let _ = write!(
+                self.body,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="{}" fill="{}" fill-opacity="{}"/>"#,
+                self.px(x), self.py(y), radius, color, opacity
+            );
+        }
+    }
+
+    /// Draws a line through the points
+    pub fn line(&mut self, points: &[(f64, f64)], color: &str, width: f64, opacity: f64) {
+        if points.is_empty() {
+            return;
+        }
+        let path: Vec<String> = points
+            .iter()
+            .map(|&(x, y)| format!("{:.1},{:.1}", self.px(x), self.py(y)))
+            .collect();
+        let _ = write!(
+            self.body,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-opacity="{}"/>"#,
+            path.join(" "), color, width, opacity
+        );
+    }
+
+    /// Draws a step line: the value of every point holds until the next one
+    pub fn step_line(&mut self, points: &[(f64, f64)], end_x: f64, color: &str, width: f64, opacity: f64) {
+        let mut steps = vec![];
+        for (i, &(x, y)) in points.iter().enumerate() {
+            steps.push((x, y));
+            let next_x = points.get(i + 1).map(|p| p.0).unwrap_or(end_x);
+            steps.push((next_x, y));
+        }
+        self.line(&steps, color, width, opacity);
+    }
+
+    /// Draws a dashed horizontal reference line
+    pub fn horizontal_line(&mut self, y: f64, color: &str) {
+        let _ = write!(
+            self.body,
+            r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-dasharray="6,4"/>"#,
+            self.px(self.x.min), self.py(y), self.px(self.x.max), self.py(y), color
+        );
+    }
+
+    /// Draws a box plot of the values at horizontal position x: the box spans
+    /// the quartiles, whiskers reach the most extreme values within 1.5 IQR
+    /// and the remaining values are drawn as outliers
+    pub fn box_plot(&mut self, x: f64, values: &[f64], color: &str) {
+        let mut sorted: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+        if sorted.is_empty() {
+            return;
+        }
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let q1 = crate::summary::quantile(&sorted, 0.25);
+        let median = crate::summary::quantile(&sorted, 0.5);
+        let q3 = crate::summary::quantile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let low = sorted.iter().copied().find(|&v| v >= q1 - 1.5 * iqr).unwrap_or(q1);
+        let high = sorted.iter().rev().copied().find(|&v| v <= q3 + 1.5 * iqr).unwrap_or(q3);
+        let half = self.pixel_width(0.3);
+        let (cx, top, bottom) = (self.px(x), self.py(q3), self.py(q1));
+        let _ = write!(
+            self.body,
+            r#"<line x1="{cx:.1}" y1="{:.1}" x2="{cx:.1}" y2="{top:.1}" stroke="black"/><line x1="{cx:.1}" y1="{bottom:.1}" x2="{cx:.1}" y2="{:.1}" stroke="black"/>"#,
+            self.py(high), self.py(low)
+        );
+        let _ = write!(
+            self.body,
+            r#"<rect x="{:.1}" y="{top:.1}" width="{:.1}" height="{:.1}" fill="{color}" fill-opacity="0.6" stroke="black"/>"#,
+            cx - half, 2.0 * half, (bottom - top).max(1.0)
+        );
+        let _ = write!(
+            self.body,
+            r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="black" stroke-width="2"/>"#,
+            cx - half, self.py(median), cx + half, self.py(median)
+        );
+        let outliers: Vec<(f64, f64)> = sorted
+            .iter()
+            .filter(|&&v| v < low || v > high)
+            .map(|&v| (x, v))
+            .collect();
+        self.points(&outliers, color, 2.5, 1.0);
+    }
+
+    /// Renders the chart as an SVG document
+    pub fn render(&self) -> String {
+        let (left, right) = (self.px(self.x.min), self.px(self.x.max));
+        let (top, bottom) = (self.py(self.y.max), self.py(self.y.min));
+        let mut svg = String::new();
+        let _ = write!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="12">"#,
+            w = self.width,
+            h = self.height
+        );
+        let _ = write!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+        let _ = write!(
+            svg,
+            r#"<text x="{:.1}" y="24" text-anchor="middle" font-size="15">{}</text>"#,
+            (left + right) / 2.0, escape(&self.title)
+        );
+        // Grid and ticks of the y axis
+        for tick in self.y.ticks() {
+            let y = self.py(tick);
+            let _ = write!(
+                svg,
+                r##"<line x1="{left:.1}" y1="{y:.1}" x2="{right:.1}" y2="{y:.1}" stroke="#dddddd"/><text x="{:.1}" y="{:.1}" text-anchor="end">{}</text>"##,
+                left - 6.0, y + 4.0, format_tick(tick)
+            );
+        }
+        // Grid and ticks (or categories) of the x axis
+        let x_ticks: Vec<(f64, String)> = if self.categories.is_empty() {
+            self.x.ticks().into_iter().map(|t| (t, format_tick(t))).collect()
+        } else {
+            self.categories.iter().enumerate().map(|(i, c)| (i as f64, escape(c))).collect()
+        };
+        for (tick, label) in x_ticks {
+            let x = self.px(tick);
+            let _ = write!(
+                svg,
+                r##"<line x1="{x:.1}" y1="{top:.1}" x2="{x:.1}" y2="{bottom:.1}" stroke="#dddddd"/><text x="{x:.1}" y="{:.1}" text-anchor="middle">{}</text>"##,
+                bottom + 18.0, label
+            );
+        }
+        let _ = write!(
+            svg,
+            r#"<rect x="{left:.1}" y="{top:.1}" width="{:.1}" height="{:.1}" fill="none" stroke="black"/>"#,
+            right - left, bottom - top
+        );
+        let _ = write!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            (left + right) / 2.0, self.height - 16.0, escape(&self.x.label)
+        );
+        let _ = write!(
+            svg,
+            r#"<text transform="translate(20,{:.1}) rotate(-90)" text-anchor="middle">{}</text>"#,
+            (top + bottom) / 2.0, escape(&self.y.label)
+        );
+        // Clip the data to the plot area
+        let id = CHART_ID.fetch_add(1, Ordering::Relaxed);
+        let _ = write!(
+            svg,
+            r#"<clipPath id="plot-area-{id}"><rect x="{left:.1}" y="{top:.1}" width="{:.1}" height="{:.1}"/></clipPath><g clip-path="url(#plot-area-{id})">{}</g>"#,
+            right - left, bottom - top, self.body
+        );
+        for (i, (label, color)) in self.legend.iter().enumerate() {
+            let y = top + 10.0 + 20.0 * i as f64;
+            let _ = write!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="12" height="12" fill="{color}"/><text x="{:.1}" y="{:.1}">{}</text>"#,
+                right + 12.0, y - 10.0, right + 30.0, y, escape(label)
+            );
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
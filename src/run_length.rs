@@ -0,0 +1,214 @@
+use crate::experiment::ExperimentResults;
+use crate::summary::{relative_gap, target_evaluation};
+
+use csv::Writer;
+use std::error::Error;
+
+/// A target value of a time-to-target analysis
+#[derive(Debug, Clone, Copy)]
+pub enum Target {
+    /// An evaluation to reach
+    Absolute(i32),
+    /// A relative gap (%) to the optimum to reach
+    Gap(f64),
+}
+
+impl Target {
+    /// Returns the evaluation to reach, None for a gap target
+    /// when the optimum is not known
+    pub fn resolve(&self, optimum: Option<i32>) -> Option<i32> {
+        match *self {
+            Target::Absolute(evaluation) => Some(evaluation),
+            Target::Gap(gap) => optimum.map(|optimum| target_evaluation(optimum, gap)),
+        }
+    }
+}
+
+/// Shifted exponential distribution F(x) = 1 - exp(-(x - shift) / scale),
+/// the usual model of run-time distributions of stochastic local search
+#[derive(Debug, Clone, Copy)]
+pub struct ShiftedExponential {
+    pub shift: f64,
+    pub scale: f64,
+}
+
+impl ShiftedExponential {
+    /// Fits the distribution to a sample with the bias-corrected
+    /// maximum likelihood estimators. Needs at least two values.
+    pub fn fit(values: &[f64]) -> Option<ShiftedExponential> {
+        let n = values.len() as f64;
+        if values.len() < 2 {
+            return None;
+        }
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let mean = values.iter().sum::<f64>() / n;
+        let scale = n / (n - 1.0) * (mean - min);
+        Some(ShiftedExponential {
+            shift: min - scale / n,
+            scale,
+        })
+    }
+
+    /// Cumulative distribution function
+    pub fn cdf(&self, x: f64) -> f64 {
+        if x <= self.shift {
+            0.0
+        } else if self.scale <= 0.0 {
+            1.0
+        } else {
+            1.0 - (-(x - self.shift) / self.scale).exp()
+        }
+    }
+
+    /// Kolmogorov-Smirnov statistic of the sample against the distribution
+    pub fn ks_statistic(&self, values: &[f64]) -> f64 {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = sorted.len() as f64;
+        sorted
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| {
+                let f = self.cdf(x);
+                (f - i as f64 / n).abs().max(((i + 1) as f64 / n - f).abs())
+            })
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Returns the empirical distribution of the values as (value, probability)
+/// pairs sorted by value. Following TTT plots, the i-th smallest value gets
+/// probability (i - 1/2) / n_runs, so runs which never reached the target
+/// keep the distribution below 1.
+pub fn ecdf(values: &[f64], n_runs: usize) -> Vec<(f64, f64)> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    sorted
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| (value, (i as f64 + 0.5) / n_runs as f64))
+        .collect()
+}
+
+/// Time-to-target and run-length (evaluations-to-target) distribution
+/// of all runs of one solver on one instance for one target
+#[derive(Debug, Clone)]
+pub struct RunLengthDistribution {
+    pub instance: String,
+    pub solver: String,
+    pub target: i32,
+    /// The gap (%) of the target to the optimum, if known
+    pub target_gap: Option<f64>,
+    pub n_runs: usize,
+    /// Time (ms) at which each successful run first reached the target
+    pub times: Vec<f64>,
+    /// Evaluations after which each successful run first reached the target
+    pub evaluations: Vec<f64>,
+    pub time_fit: Option<ShiftedExponential>,
+    pub evaluations_fit: Option<ShiftedExponential>,
+}
+
+impl RunLengthDistribution {
+    /// Collects the first hits of the target from the traces of the runs.
+    /// Returns None for a gap target when the optimum is not known.
+    pub fn new(
+        instance: &str,
+        solver: &str,
+        results: &ExperimentResults,
+        target: Target,
+        optimum: Option<i32>,
+    ) -> Option<RunLengthDistribution> {
+        let target = target.resolve(optimum)?;
+        let hits: Vec<_> = results.first_hits(target).into_iter().flatten().collect();
+        let times: Vec<f64> = hits.iter().map(|hit| hit.time).collect();
+        let evaluations: Vec<f64> = hits.iter().map(|hit| hit.evaluations as f64).collect();
+        Some(RunLengthDistribution {
+            instance: instance.to_string(),
+            solver: solver.to_string(),
+            target,
+            target_gap: optimum.map(|optimum| relative_gap(target, optimum)),
+            n_runs: results.n_runs(),
+            time_fit: ShiftedExponential::fit(&times),
+            evaluations_fit: ShiftedExponential::fit(&evaluations),
+            times,
+            evaluations,
+        })
+    }
+
+    /// Returns the fraction of runs which reached the target
+    pub fn success_rate(&self) -> f64 {
+        self.times.len() as f64 / self.n_runs.max(1) as f64
+    }
+}
+
+/// Saves the empirical distributions in a csv file with a row per
+/// successful run per measure ("time" or "evaluations"), together
+/// with the value of the fitted distribution
+pub fn save_ecdf_tables(distributions: &[RunLengthDistribution], path: &str) -> Result<(), Box<dyn Error>> {
+    let mut wtr = Writer::from_path(path)?;
+    // Write column names
+    wtr.write_record([
+        "instance", "solver", "target", "target_gap", "measure", "value", "probability", "fitted",
+    ])?;
+    for distribution in distributions {
+        let measures = [
+            ("time", &distribution.times, &distribution.time_fit),
+            ("evaluations", &distribution.evaluations, &distribution.evaluations_fit),
+        ];
+        for (measure, values, fit) in measures {
+            for (value, probability) in ecdf(values, distribution.n_runs) {
+                wtr.write_record(&[
+                    distribution.instance.clone(),
+                    distribution.solver.clone(),
+                    distribution.target.to_string(),
+                    format_option(distribution.target_gap),
+                    measure.to_string(),
+                    value.to_string(),
+                    probability.to_string(),
+                    format_option(fit.map(|fit| fit.cdf(value))),
+                ])?;
+            }
+        }
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Saves the success rates and the fitted shifted exponential
+/// distributions in a csv file with a row per distribution
+pub fn save_fit_summaries(distributions: &[RunLengthDistribution], path: &str) -> Result<(), Box<dyn Error>> {
+    let mut wtr = Writer::from_path(path)?;
+    // Write column names
+    wtr.write_record([
+        "instance", "solver", "target", "target_gap", "n_runs", "n_hits", "success_rate",
+        "time_shift", "time_scale", "time_ks", "evaluations_shift", "evaluations_scale", "evaluations_ks",
+    ])?;
+    for distribution in distributions {
+        let mut record = vec![
+            distribution.instance.clone(),
+            distribution.solver.clone(),
+            distribution.target.to_string(),
+            format_option(distribution.target_gap),
+            distribution.n_runs.to_string(),
+            distribution.times.len().to_string(),
+            distribution.success_rate().to_string(),
+        ];
+        let fits = [
+            (&distribution.time_fit, &distribution.times),
+            (&distribution.evaluations_fit, &distribution.evaluations),
+        ];
+        for (fit, values) in fits {
+            record.push(format_option(fit.map(|fit| fit.shift)));
+            record.push(format_option(fit.map(|fit| fit.scale)));
+            record.push(format_option(fit.map(|fit| fit.ks_statistic(values))));
+        }
+        wtr.write_record(&record)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Formats an optional value, None as an empty field
+fn format_option(value: Option<f64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
@@ -31,6 +31,8 @@
 
 use core::fmt;
 use std::f32::INFINITY;
+use std::fs;
+use std::io::{Error, ErrorKind};
 
 #[derive(Debug, Default, Clone)]
 /// A solution is represented here
@@ -54,6 +56,50 @@ impl Solution {
             n,
         }
     }
+    /// Reads a solution from a QAPLIB .sln file. The file stores the
+    /// instance size, the objective value and a 1-based permutation
+    /// (separated by whitespace or commas).
+    /// The returned solution is 0-based and its evaluation is set
+    /// to the objective value from the file.
+    pub fn read_sln(filename: &str) -> Result<Solution, Error> {
+        let content = fs::read_to_string(filename)?;
+        let invalid = |msg: &str| Error::new(ErrorKind::InvalidData, format!("{}: {}", filename, msg));
+        let mut numbers = content
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<i64>());
+        let mut next_number = || match numbers.next() {
+            Some(Ok(number)) => Ok(number),
+            Some(Err(_)) => Err(invalid("not a number")),
+            None => Err(invalid("unexpected end of file")),
+        };
+        let n = next_number()? as usize;
+        let eval = next_number()? as i32;
+        let mut solution_array = Vec::with_capacity(n);
+        for _ in 0..n {
+            let facility = next_number()?;
+            if facility < 1 || facility > n as i64 {
+                return Err(invalid("facility out of range"));
+            }
+            solution_array.push(facility as usize - 1);
+        }
+        let mut solution = Solution::new(solution_array);
+        solution.set_eval(eval);
+        Ok(solution)
+    }
+
+    /// Writes the solution to a QAPLIB .sln file: the instance size and
+    /// the evaluation, followed by the 1-based permutation
+    pub fn write_sln(&self, filename: &str) -> Result<(), Error> {
+        let mut content = format!("{} {}\n", self.solution_array.len(), self.eval);
+        for row in self.solution_array.chunks(20) {
+            let row: Vec<String> = row.iter().map(|facility| (facility + 1).to_string()).collect();
+            content += &row.join(" ");
+            content += "\n";
+        }
+        fs::write(filename, content)
+    }
+
     /// Returns the evaluation.
     /// evaluate() must be called first.
     pub fn get_eval(&self) -> i32 {
@@ -69,6 +115,52 @@ impl Solution {
         self.solution_array.clone()
     }
 
+    /// Returns the Hamming distance to another solution: the number
+    /// of locations with different facilities
+    pub fn hamming_distance(&self, other: &Solution) -> usize {
+        self.solution_array
+            .iter()
+            .zip(&other.solution_array)
+            .filter(|(a, b)| a != b)
+            .count()
+    }
+
+    /// Returns the Cayley distance to another solution: the smallest
+    /// number of swaps transforming one into the other, n minus the
+    /// number of cycles of the permutation mapping one to the other
+    pub fn cayley_distance(&self, other: &Solution) -> usize {
+        let n = self.solution_array.len();
+        // Location of every facility in the other solution
+        let mut location = vec![0; n];
+        for (l, &facility) in other.solution_array.iter().enumerate() {
+            location[facility] = l;
+        }
+        let mut visited = vec![false; n];
+        let mut cycles = 0;
+        for start in 0..n {
+            if visited[start] {
+                continue;
+            }
+            cycles += 1;
+            let mut l = start;
+            while !visited[l] {
+                visited[l] = true;
+                l = location[self.solution_array[l]];
+            }
+        }
+        n - cycles
+    }
+
+    /// Returns the overlap similarity with another solution: the
+    /// fraction of locations with the same facility
+    pub fn overlap(&self, other: &Solution) -> f64 {
+        let n = self.solution_array.len();
+        if n == 0 {
+            return 1.0;
+        }
+        (n - self.hamming_distance(other)) as f64 / n as f64
+    }
+
     /// Evaluates the solution based on the provided matrices A and B (references)
     pub fn evaluate(&mut self, matrix_a: &Vec<Vec<i32>>, matrix_b: &Vec<Vec<i32>>) -> i32 {
         let n: usize = self.solution_array.len();
@@ -101,8 +193,8 @@ impl Solution {
     /// Calculates delta of a pair exchange in O(N) time
     pub fn calculate_delta(
         &self,
-        matrix_a: &Vec<Vec<i32>>,
-        matrix_b: &Vec<Vec<i32>>,
+        matrix_a: &[Vec<i32>],
+        matrix_b: &[Vec<i32>],
         pair: &[usize; 2],
     ) -> i32 {
         let mut delta: i32 = 0;
@@ -0,0 +1,126 @@
+use rand::rngs::ThreadRng;
+
+use crate::acceptance::AcceptanceCriterion;
+use crate::neighborhood::SwapSampler;
+use crate::qap_problem::QapProblem;
+use crate::solution::Solution;
+use crate::solvers::solver::Solver;
+use crate::trace::Trace;
+use crate::utils::*;
+
+/// Acceptance-based local search: the swaps are sampled and evaluated
+/// like in simulated annealing, a deterministic criterion (great deluge,
+/// threshold accepting, record-to-record travel, late acceptance)
+/// decides which moves are accepted
+pub struct AcceptanceSolver<'a> {
+    problem: &'a QapProblem,
+    /// Samples the swaps in a fixed cyclic order
+    sampler: SwapSampler,
+    rng: ThreadRng,
+    iter_count: i32,
+    update_count: i32, // The number of times a solution is updated
+    initial_solution: Option<Solution>,
+    /// Decides which moves are accepted
+    criterion: Box<dyn AcceptanceCriterion>,
+    /// Stopping criterion: iterations without improvement of the best solution
+    max_no_improvement: usize,
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
+}
+
+impl<'a> AcceptanceSolver<'a> {
+    // Constructor
+    pub fn new(
+        problem: &'a QapProblem,
+        criterion: Box<dyn AcceptanceCriterion>,
+        max_no_improvement: usize,
+    ) -> AcceptanceSolver<'a> {
+        AcceptanceSolver {
+            problem,
+            sampler: SwapSampler::new(problem.get_n()),
+            rng: rand::thread_rng(),
+            iter_count: 0,
+            update_count: 0,
+            initial_solution: None,
+            criterion,
+            max_no_improvement,
+            trace: None,
+        }
+    }
+
+    /// Returns the name of the acceptance criterion
+    pub fn criterion_name(&self) -> &'static str {
+        self.criterion.name()
+    }
+
+    fn generate_random_solution(&mut self) -> Solution {
+        let n = self.problem.get_n();
+        let mut solution_array = vec![0; n];
+        arange(&mut solution_array, 0, 1);
+        permute_array(&mut self.rng, &mut solution_array);
+        Solution::new(solution_array)
+    }
+
+    /// Runs the search from the initial solution and returns
+    /// the best visited solution, evaluated incrementally
+    pub fn solve_acceptance(&mut self, mut initial_solution: Solution) -> Solution {
+        let (matrix_a, matrix_b) = (self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+        initial_solution.evaluate(matrix_a, matrix_b);
+        self.initial_solution = Some(initial_solution.clone());
+        let mut current_solution = initial_solution;
+        let mut current_score = current_solution.get_eval();
+        let mut best_solution = current_solution.clone();
+        if let Some(trace) = &mut self.trace {
+            trace.update(1, current_score);
+        }
+
+        self.iter_count = 0;
+        self.update_count = 0;
+        self.criterion.reset(current_score);
+        self.sampler.restart(&mut self.rng);
+        // the number of iterations without improvement of the best solution
+        let mut p = 0;
+        while p < self.max_no_improvement {
+            let (pair, delta) = self.sampler.next_move(&current_solution, matrix_a, matrix_b);
+            p += 1;
+            if self.criterion.accept(current_score, current_score + delta, best_solution.get_eval()) {
+                current_solution.exchange_facilities(&pair);
+                current_score += delta;
+                self.update_count += 1;
+                if current_score < best_solution.get_eval() {
+                    best_solution.solution_array.clone_from(&current_solution.solution_array);
+                    best_solution.set_eval(current_score);
+                    p = 0;
+                }
+            }
+            self.criterion.update(current_score, best_solution.get_eval());
+            if let Some(trace) = &mut self.trace {
+                trace.update(1, current_score);
+            }
+            self.iter_count += 1;
+        }
+        best_solution
+    }
+}
+
+impl<'a> Solver for AcceptanceSolver<'a> {
+    fn solve(&mut self) -> Solution {
+        let initial_solution = self.generate_random_solution();
+        self.solve_acceptance(initial_solution)
+    }
+    fn get_iter_count(&self) -> i32 {
+        self.iter_count
+    }
+    fn get_update_count(&self) -> i32 {
+        self.update_count
+    }
+    fn get_initial_solution(&self) -> Option<Solution> {
+        self.initial_solution.clone()
+    }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+}
@@ -0,0 +1,119 @@
+use rand::rngs::ThreadRng;
+
+use crate::qap_problem::QapProblem;
+use crate::solution::Solution;
+use crate::solvers::solver::Solver;
+use crate::trace::Trace;
+use crate::utils::*;
+
+/// First-improvement local search over swaps with don't-look bits.
+/// Locations are scanned circularly, a location whose swaps did not
+/// improve the solution is skipped until one of its swaps is applied.
+/// Swaps of skipped locations are not re-evaluated, so the result is
+/// a local optimum only up to the don't-look bits.
+pub struct FirstImprovementLSSolver<'a> {
+    problem: &'a QapProblem,
+    rng: ThreadRng,
+    iter_count: i32,   // The number of evaluated moves
+    update_count: i32, // The number of times a solution is updated
+    initial_solution: Option<Solution>,
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
+}
+
+impl<'a> FirstImprovementLSSolver<'a> {
+    // Constructor
+    pub fn new(problem: &'a QapProblem) -> FirstImprovementLSSolver<'a> {
+        FirstImprovementLSSolver {
+            problem,
+            rng: rand::thread_rng(),
+            iter_count: 0,
+            update_count: 0,
+            initial_solution: None,
+            trace: None,
+        }
+    }
+
+    fn generate_random_solution(&mut self) -> Solution {
+        let n = self.problem.get_n();
+        let mut solution_array = vec![0; n];
+        arange(&mut solution_array, 0, 1);
+        permute_array(&mut self.rng, &mut solution_array);
+        Solution::new(solution_array)
+    }
+
+    /// Runs the first-improvement descent from the initial solution
+    /// and returns the local optimum
+    pub fn solve_first_improvement(&mut self, mut initial_solution: Solution) -> Solution {
+        let (matrix_a, matrix_b) = (self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+        initial_solution.evaluate(matrix_a, matrix_b);
+        self.initial_solution = Some(initial_solution.clone());
+        let mut current_solution = initial_solution;
+        if let Some(trace) = &mut self.trace {
+            trace.update(1, current_solution.get_eval());
+        }
+
+        let n = self.problem.get_n();
+        let mut dont_look = vec![false; n];
+        let mut iter_count = 0;
+        self.update_count = 0;
+        // The scan stops after a full round of locations without improvement
+        let mut i = 0;
+        let mut unimproved = 0;
+        while unimproved < n && n > 1 {
+            let mut improved = false;
+            if !dont_look[i] {
+                for offset in 1..n {
+                    let j = (i + offset) % n;
+                    let pair = [i.min(j), i.max(j)];
+                    let delta = current_solution.calculate_delta(matrix_a, matrix_b, &pair);
+                    iter_count += 1;
+                    if let Some(trace) = &mut self.trace {
+                        trace.update(1, current_solution.get_eval() + delta.min(0));
+                    }
+                    if delta < 0 {
+                        current_solution.exchange_facilities(&pair);
+                        current_solution.set_eval(current_solution.get_eval() + delta);
+                        self.update_count += 1;
+                        dont_look[j] = false;
+                        improved = true;
+                        break;
+                    }
+                }
+                dont_look[i] = !improved;
+            }
+            if improved {
+                // Look at the same location again
+                unimproved = 0;
+            } else {
+                unimproved += 1;
+                i = (i + 1) % n;
+            }
+        }
+        self.iter_count = iter_count;
+        current_solution.evaluate(matrix_a, matrix_b);
+        current_solution
+    }
+}
+
+impl<'a> Solver for FirstImprovementLSSolver<'a> {
+    fn solve(&mut self) -> Solution {
+        let initial_solution = self.generate_random_solution();
+        self.solve_first_improvement(initial_solution)
+    }
+    fn get_iter_count(&self) -> i32 {
+        self.iter_count
+    }
+    fn get_update_count(&self) -> i32 {
+        self.update_count
+    }
+    fn get_initial_solution(&self) -> Option<Solution> {
+        self.initial_solution.clone()
+    }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+}
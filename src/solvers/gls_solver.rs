@@ -0,0 +1,217 @@
+use rand::rngs::ThreadRng;
+
+use crate::qap_problem::QapProblem;
+use crate::solution::Solution;
+use crate::solvers::solver::Solver;
+use crate::trace::Trace;
+use crate::utils::*;
+
+/// Settings of guided local search
+#[derive(Debug, Clone, Copy)]
+pub struct GLSSettings {
+    /// The penalty weight, as a fraction of the mean assignment cost
+    /// of the first local optimum
+    pub alpha: f64,
+    /// Stopping criterion: penalty rounds without improvement of the best solution
+    pub max_no_improvement: usize,
+}
+
+impl Default for GLSSettings {
+    fn default() -> Self {
+        GLSSettings {
+            alpha: 0.05,
+            max_no_improvement: 500,
+        }
+    }
+}
+
+/// Guided local search. The features are the facility-to-location
+/// assignments. At every local optimum of the augmented objective
+/// f(s) + lambda * sum of the penalties of the assignments of s, the
+/// assignments with the largest utility (cost contribution divided by
+/// one plus the penalty) are penalized. The descent is a first-improvement
+/// swap search with don't-look bits, only the locations of penalized
+/// assignments are looked at again after a penalty round.
+pub struct GLSSolver<'a> {
+    problem: &'a QapProblem,
+    rng: ThreadRng,
+    iter_count: i32,   // The number of evaluated moves
+    update_count: i32, // The number of times a solution is updated
+    initial_solution: Option<Solution>,
+    settings: GLSSettings,
+    /// penalties[location][facility] of the assignments
+    penalties: Vec<Vec<i32>>,
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
+}
+
+impl<'a> GLSSolver<'a> {
+    // Constructor
+    pub fn new(problem: &'a QapProblem, settings: GLSSettings) -> GLSSolver<'a> {
+        GLSSolver {
+            problem,
+            rng: rand::thread_rng(),
+            iter_count: 0,
+            update_count: 0,
+            initial_solution: None,
+            settings,
+            penalties: vec![],
+            trace: None,
+        }
+    }
+
+    fn generate_random_solution(&mut self) -> Solution {
+        let n = self.problem.get_n();
+        let mut solution_array = vec![0; n];
+        arange(&mut solution_array, 0, 1);
+        permute_array(&mut self.rng, &mut solution_array);
+        Solution::new(solution_array)
+    }
+
+    /// Returns the penalties of the assignments after the last run
+    pub fn get_penalties(&self) -> &[Vec<i32>] {
+        &self.penalties
+    }
+
+    /// Returns the change of the penalty term caused by swapping
+    /// the facilities of a pair of locations
+    fn penalty_delta(&self, solution: &Solution, pair: &[usize; 2]) -> i64 {
+        let s = &solution.solution_array;
+        let [i, j] = *pair;
+        (self.penalties[i][s[j]] + self.penalties[j][s[i]] - self.penalties[i][s[i]] - self.penalties[j][s[j]]) as i64
+    }
+
+    /// Returns the contributions of the assignments to the objective: the
+    /// terms of the row and the column of every location
+    fn contributions(&self, solution: &Solution) -> Vec<i64> {
+        let (matrix_a, matrix_b) = (self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+        let s = &solution.solution_array;
+        (0..s.len())
+            .map(|i| {
+                (0..s.len())
+                    .map(|j| (matrix_a[i][j] * matrix_b[s[i]][s[j]] + matrix_a[j][i] * matrix_b[s[j]][s[i]]) as i64)
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Runs the first-improvement descent of the augmented objective from
+    /// the current solution, updating its (true) evaluation incrementally
+    /// and the best solution. Returns true if the best solution improved.
+    fn descend(&mut self, current_solution: &mut Solution, best_solution: &mut Solution, active: &mut [bool], lambda: i64) -> bool {
+        let (matrix_a, matrix_b) = (self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+        let n = active.len();
+        let mut improved_best = false;
+        // The scan stops after a full round of locations without improvement
+        let mut i = 0;
+        let mut unimproved = 0;
+        while unimproved < n && n > 1 {
+            let mut improved = false;
+            if active[i] {
+                for offset in 1..n {
+                    let j = (i + offset) % n;
+                    let pair = [i.min(j), i.max(j)];
+                    let delta = current_solution.calculate_delta(matrix_a, matrix_b, &pair);
+                    self.iter_count += 1;
+                    improved = delta as i64 + lambda * self.penalty_delta(current_solution, &pair) < 0;
+                    if improved {
+                        current_solution.exchange_facilities(&pair);
+                        current_solution.set_eval(current_solution.get_eval() + delta);
+                        self.update_count += 1;
+                        active[j] = true;
+                        if current_solution.get_eval() < best_solution.get_eval() {
+                            best_solution.clone_from(current_solution);
+                            improved_best = true;
+                        }
+                    }
+                    if let Some(trace) = &mut self.trace {
+                        trace.update(1, current_solution.get_eval());
+                    }
+                    if improved {
+                        break;
+                    }
+                }
+                active[i] = improved;
+            }
+            if improved {
+                // Look at the same location again
+                unimproved = 0;
+            } else {
+                unimproved += 1;
+                i = (i + 1) % n;
+            }
+        }
+        improved_best
+    }
+
+    /// Penalizes the assignments of maximum utility and activates their locations
+    fn penalize(&mut self, solution: &Solution, active: &mut [bool]) {
+        let s = &solution.solution_array;
+        let utilities: Vec<f64> = self
+            .contributions(solution)
+            .iter()
+            .enumerate()
+            .map(|(i, &cost)| cost as f64 / (1 + self.penalties[i][s[i]]) as f64)
+            .collect();
+        let max_utility = utilities.iter().cloned().fold(f64::MIN, f64::max);
+        for (i, &utility) in utilities.iter().enumerate() {
+            if utility == max_utility {
+                self.penalties[i][s[i]] += 1;
+                active[i] = true;
+            }
+        }
+    }
+
+    /// Runs guided local search from the initial solution and
+    /// returns the best solution, evaluated incrementally
+    pub fn solve_guided_local_search(&mut self, mut initial_solution: Solution) -> Solution {
+        initial_solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+        self.initial_solution = Some(initial_solution.clone());
+        let n = self.problem.get_n();
+        self.penalties = vec![vec![0; n]; n];
+        self.iter_count = 0;
+        self.update_count = 0;
+        let mut current_solution = initial_solution.clone();
+        let mut best_solution = initial_solution;
+        if let Some(trace) = &mut self.trace {
+            trace.update(1, best_solution.get_eval());
+        }
+
+        let mut active = vec![true; n];
+        self.descend(&mut current_solution, &mut best_solution, &mut active, 0);
+        // The penalty weight is a fraction of the mean assignment cost of the first local optimum
+        let lambda = ((self.settings.alpha * current_solution.get_eval() as f64 / n.max(1) as f64).round() as i64).max(1);
+        // the number of penalty rounds without improvement of the best solution
+        let mut p = 0;
+        while p < self.settings.max_no_improvement {
+            self.penalize(&current_solution, &mut active);
+            p += 1;
+            if self.descend(&mut current_solution, &mut best_solution, &mut active, lambda) {
+                p = 0;
+            }
+        }
+        best_solution
+    }
+}
+
+impl<'a> Solver for GLSSolver<'a> {
+    fn solve(&mut self) -> Solution {
+        let initial_solution = self.generate_random_solution();
+        self.solve_guided_local_search(initial_solution)
+    }
+    fn get_iter_count(&self) -> i32 {
+        self.iter_count
+    }
+    fn get_update_count(&self) -> i32 {
+        self.update_count
+    }
+    fn get_initial_solution(&self) -> Option<Solution> {
+        self.initial_solution.clone()
+    }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+}
@@ -6,6 +6,7 @@ use rand::seq::index;
 use crate::qap_problem::QapProblem;
 use crate::solution::Solution;
 use crate::solvers::solver::Solver;
+use crate::trace::Trace;
 use crate::utils::*;
 use rand::Rng;
 
@@ -15,6 +16,8 @@ pub struct HeuristicSolver<'a> {
     time_limit: u128,
     iter_count: i32,   // The number of times the LS loop is ran
     update_count: i32, // The number of times a solution is updated
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
 }
 
 impl<'a> HeuristicSolver<'a> {
@@ -32,6 +35,7 @@ impl<'a> HeuristicSolver<'a> {
             time_limit,
             iter_count,
             update_count,
+            trace: None,
         }
     }
 
@@ -134,6 +138,9 @@ impl<'a> Solver for HeuristicSolver<'a> {
         let mut best_solution = Solution::new(solution.get_solution_array());
         best_solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
         let mut best_score = solution.get_eval();
+        if let Some(trace) = &mut self.trace {
+            trace.update(1, best_score);
+        }
 
         let start = Instant::now();
         let mut elapsed: u128 = 0;
@@ -144,6 +151,9 @@ impl<'a> Solver for HeuristicSolver<'a> {
             solution = self.solve_heuristic();
 
             let score = solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+            if let Some(trace) = &mut self.trace {
+                trace.update(1, score);
+            }
             if score < best_score {
                 best_score = score;
                 best_solution = Solution::new(solution.get_solution_array());
@@ -164,4 +174,10 @@ impl<'a> Solver for HeuristicSolver<'a> {
     fn get_initial_solution(&self) -> Option<Solution> {
         None
     }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
 }
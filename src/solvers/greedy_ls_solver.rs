@@ -3,6 +3,7 @@ use rand::rngs::ThreadRng;
 use crate::qap_problem::QapProblem;
 use crate::solution::Solution;
 use crate::solvers::solver::Solver;
+use crate::trace::Trace;
 use crate::utils::*;
 
 pub struct GreedyLSSolver<'a> {
@@ -12,6 +13,8 @@ pub struct GreedyLSSolver<'a> {
     iter_count: i32,   // The number of times the LS loop is ran
     update_count: i32, // The number of times a solution is updated
     initial_solution: Option<Solution>,
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
 }
 
 impl<'a> GreedyLSSolver<'a> {
@@ -30,6 +33,7 @@ impl<'a> GreedyLSSolver<'a> {
             iter_count,
             update_count,
             initial_solution,
+            trace: None,
         }
     }
 
@@ -41,13 +45,18 @@ impl<'a> GreedyLSSolver<'a> {
         Solution::new(solution_array)
     }
 
-    fn solve_greedy(&mut self, initial_solution: Solution) -> Solution {
+    /// Runs the greedy descent from the initial solution
+    /// and returns the local optimum
+    pub fn solve_greedy(&mut self, initial_solution: Solution) -> Solution {
         let mut initial_solution = initial_solution;
         initial_solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
         self.initial_solution = Some(Solution::new(initial_solution.get_solution_array()));
         self.initial_solution.as_mut().unwrap().set_eval(initial_solution.get_eval());
         
         let mut current_solution = initial_solution;
+        if let Some(trace) = &mut self.trace {
+            trace.update(1, current_solution.get_eval());
+        }
         // println!("LS initial solution: {}", current_solution);
         // Randomize the order of pairs
         permute_array(&mut self.rng, &mut self.candidate_moves);
@@ -61,8 +70,12 @@ impl<'a> GreedyLSSolver<'a> {
                 self.problem.matrix_b_ref(),
                 &pair,
             );
+            if let Some(trace) = &mut self.trace {
+                trace.update(1, current_solution.get_eval() + delta.min(0));
+            }
             if delta < 0 {
                 current_solution.exchange_facilities(&pair);
+                current_solution.set_eval(current_solution.get_eval() + delta);
                 permute_array(&mut self.rng, &mut self.candidate_moves);
                 i = 0;
                 self.update_count = self.update_count + 1;
@@ -95,4 +108,10 @@ impl<'a> Solver for GreedyLSSolver<'a> {
     fn get_initial_solution(&self) -> Option<Solution> {
         self.initial_solution.clone()
     }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
 }
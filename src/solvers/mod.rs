@@ -1,8 +1,14 @@
 pub mod random_solver;
+pub mod acceptance_solver;
 pub mod greedy_ls_solver;
+pub mod first_improvement_solver;
 pub mod solver;
 pub mod steepest_ls_solver;
 pub mod random_walk_solver;
 pub mod greedy_heuristic_solver;
 pub mod tabu_search_solver;
-pub mod simulated_annealing_solver;
\ No newline at end of file
+pub mod simulated_annealing_solver;
+pub mod parallel_tempering_solver;
+pub mod vns_solver;
+pub mod gls_solver;
+pub mod registry;
\ No newline at end of file
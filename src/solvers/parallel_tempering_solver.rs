@@ -0,0 +1,291 @@
+use rand::rngs::{StdRng, ThreadRng};
+use rand::{Rng, SeedableRng};
+
+use crate::cooling::{metropolis_accept, temperature_for_acceptance};
+use crate::neighborhood::SwapSampler;
+use crate::parallel::parallel_for_each;
+use crate::qap_problem::QapProblem;
+use crate::solution::Solution;
+use crate::solvers::solver::Solver;
+use crate::trace::Trace;
+use crate::utils::*;
+
+/// Settings of parallel tempering
+#[derive(Debug, Clone, Copy)]
+pub struct PTSettings {
+    /// The number of replicas (temperatures of the ladder)
+    pub n_replicas: usize,
+    /// The acceptance ratio of random worsening swaps at the hottest temperature
+    pub max_acceptance: f64,
+    /// The acceptance ratio of random worsening swaps at the coldest temperature
+    pub min_acceptance: f64,
+    /// Every replica tries the number of swaps divided by l_div between exchanges
+    pub l_div: usize,
+    /// Stopping criterion: exchange rounds without improvement of the best solution
+    pub max_no_improvement: usize,
+    /// The number of exchange rounds between adaptations of the ladder, 0 disables them
+    pub adapt_interval: usize,
+    /// The number of threads running the replicas
+    pub n_threads: usize,
+}
+
+impl Default for PTSettings {
+    fn default() -> Self {
+        PTSettings {
+            n_replicas: 8,
+            max_acceptance: 0.5,
+            min_acceptance: 0.0001,
+            l_div: 4,
+            max_no_improvement: 100,
+            adapt_interval: 10,
+            n_threads: 1,
+        }
+    }
+}
+
+/// A Metropolis search at one temperature of the ladder
+struct Replica {
+    current_solution: Solution,
+    best_solution: Solution,
+    sampler: SwapSampler,
+    rng: StdRng,
+    temperature: f32,
+    accepted: i32,
+}
+
+impl Replica {
+    /// Tries `length` swaps at the temperature of the replica
+    fn sweep(&mut self, length: usize, matrix_a: &[Vec<i32>], matrix_b: &[Vec<i32>]) {
+        for _ in 0..length {
+            let (pair, delta) = self.sampler.next_move(&self.current_solution, matrix_a, matrix_b);
+            if metropolis_accept(delta, self.temperature, &mut self.rng) {
+                self.current_solution.exchange_facilities(&pair);
+                self.current_solution.set_eval(self.current_solution.get_eval() + delta);
+                self.accepted += 1;
+                if self.current_solution.get_eval() < self.best_solution.get_eval() {
+                    self.best_solution.clone_from(&self.current_solution);
+                }
+            }
+        }
+    }
+}
+
+/// Parallel tempering (replica exchange): Metropolis searches at a ladder
+/// of temperatures, optionally on separate threads. After every sweep,
+/// the solutions of neighbouring temperatures are exchanged by the
+/// Metropolis rule, alternating even and odd pairs, and the ladder is
+/// adapted to balance the exchange acceptance rates.
+pub struct PTSolver<'a> {
+    problem: &'a QapProblem,
+    rng: ThreadRng,
+    iter_count: i32,
+    update_count: i32, // The number of times a solution is updated
+    initial_solution: Option<Solution>,
+    settings: PTSettings,
+    /// The temperatures of the last run, from the coldest
+    ladder: Vec<f32>,
+    /// The exchange acceptance rates of neighbouring temperatures in the last run
+    exchange_rates: Vec<f64>,
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
+}
+
+impl<'a> PTSolver<'a> {
+    // Constructor
+    pub fn new(problem: &'a QapProblem, settings: PTSettings) -> PTSolver<'a> {
+        PTSolver {
+            problem,
+            rng: rand::thread_rng(),
+            iter_count: 0,
+            update_count: 0,
+            initial_solution: None,
+            settings,
+            ladder: vec![],
+            exchange_rates: vec![],
+            trace: None,
+        }
+    }
+
+    fn generate_random_solution(&mut self) -> Solution {
+        let n = self.problem.get_n();
+        let mut solution_array = vec![0; n];
+        arange(&mut solution_array, 0, 1);
+        permute_array(&mut self.rng, &mut solution_array);
+        Solution::new(solution_array)
+    }
+
+    /// Returns a geometric ladder between the temperatures accepting the
+    /// minimum and the maximum ratio of random worsening swaps
+    fn initial_ladder(&mut self) -> Vec<f32> {
+        let sampler = SwapSampler::new(self.problem.get_n());
+        let mut deltas = Vec::new();
+        for _ in 0..1000 {
+            let mut solution = self.generate_random_solution();
+            solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+            let Some(pair) = sampler.random_move(&mut self.rng) else {
+                break;
+            };
+            deltas.push(solution.calculate_delta(self.problem.matrix_a_ref(), self.problem.matrix_b_ref(), &pair));
+        }
+        let coldest = temperature_for_acceptance(&deltas, self.settings.min_acceptance);
+        let hottest = temperature_for_acceptance(&deltas, self.settings.max_acceptance).max(coldest);
+        let n_replicas = self.settings.n_replicas.max(1);
+        (0..n_replicas)
+            .map(|k| coldest * (hottest / coldest).powf(k as f32 / (n_replicas - 1).max(1) as f32))
+            .collect()
+    }
+
+    /// Returns the temperatures of the last run, from the coldest
+    pub fn get_ladder(&self) -> &[f32] {
+        &self.ladder
+    }
+
+    /// Returns the exchange acceptance rates of neighbouring temperatures in the last run
+    pub fn get_exchange_rates(&self) -> &[f64] {
+        &self.exchange_rates
+    }
+
+    /// Runs the replicas from the initial solution and returns the
+    /// best solution across replicas, evaluated incrementally
+    pub fn solve_parallel_tempering(&mut self, mut initial_solution: Solution) -> Solution {
+        let (matrix_a, matrix_b) = (self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+        initial_solution.evaluate(matrix_a, matrix_b);
+        self.initial_solution = Some(initial_solution.clone());
+        let mut best_solution = initial_solution.clone();
+        if let Some(trace) = &mut self.trace {
+            trace.update(1, best_solution.get_eval());
+        }
+
+        let mut ladder = self.initial_ladder();
+        let n = self.problem.get_n();
+        let mut replicas: Vec<Replica> = ladder
+            .iter()
+            .map(|&temperature| {
+                let mut rng = StdRng::seed_from_u64(self.rng.gen());
+                let mut sampler = SwapSampler::new(n);
+                sampler.restart(&mut rng);
+                Replica {
+                    current_solution: initial_solution.clone(),
+                    best_solution: initial_solution.clone(),
+                    sampler,
+                    rng,
+                    temperature,
+                    accepted: 0,
+                }
+            })
+            .collect();
+        let sweep_length = (n * n.saturating_sub(1) / 2 / self.settings.l_div.max(1)).max(1);
+        let n_pairs = replicas.len() - 1;
+        // Exchange attempts and acceptances of every pair, in total and since the last adaptation
+        let (mut attempts, mut accepted) = (vec![0usize; n_pairs], vec![0usize; n_pairs]);
+        let (mut recent_attempts, mut recent_accepted) = (vec![0usize; n_pairs], vec![0usize; n_pairs]);
+
+        self.iter_count = 0;
+        // the number of exchange rounds without improvement of the best solution
+        let mut p = 0;
+        let mut round = 0;
+        while p < self.settings.max_no_improvement {
+            parallel_for_each(&mut replicas, self.settings.n_threads, |replica| {
+                replica.sweep(sweep_length, matrix_a, matrix_b)
+            });
+            self.iter_count += (sweep_length * replicas.len()) as i32;
+
+            p += 1;
+            for replica in &replicas {
+                if replica.best_solution.get_eval() < best_solution.get_eval() {
+                    best_solution.clone_from(&replica.best_solution);
+                    p = 0;
+                }
+            }
+            if let Some(trace) = &mut self.trace {
+                trace.update((sweep_length * replicas.len()) as u64, best_solution.get_eval());
+            }
+
+            // Exchange the solutions of the even pairs in even rounds, odd pairs in odd rounds
+            for k in (round % 2..n_pairs).step_by(2) {
+                let (cold, hot) = (&replicas[k], &replicas[k + 1]);
+                let energy = (cold.current_solution.get_eval() - hot.current_solution.get_eval()) as f64;
+                let beta = 1.0 / cold.temperature as f64 - 1.0 / hot.temperature as f64;
+                // Accepted with probability min(1, exp((E_cold - E_hot) * (1/T_cold - 1/T_hot)))
+                let probability = (energy * beta).min(0.0).exp();
+                attempts[k] += 1;
+                recent_attempts[k] += 1;
+                if self.rng.gen_range(0.0..1.0) < probability {
+                    let (left, right) = replicas.split_at_mut(k + 1);
+                    std::mem::swap(&mut left[k].current_solution, &mut right[0].current_solution);
+                    accepted[k] += 1;
+                    recent_accepted[k] += 1;
+                }
+            }
+            round += 1;
+
+            if self.settings.adapt_interval > 0 && round % self.settings.adapt_interval == 0 {
+                let rates: Vec<f64> = recent_accepted
+                    .iter()
+                    .zip(&recent_attempts)
+                    .map(|(&a, &t)| a as f64 / t.max(1) as f64)
+                    .collect();
+                ladder = adapt_ladder(&ladder, &rates);
+                for (replica, &temperature) in replicas.iter_mut().zip(&ladder) {
+                    replica.temperature = temperature;
+                }
+                recent_attempts.iter_mut().for_each(|a| *a = 0);
+                recent_accepted.iter_mut().for_each(|a| *a = 0);
+            }
+        }
+        self.update_count = replicas.iter().map(|replica| replica.accepted).sum();
+        self.exchange_rates = accepted.iter().zip(&attempts).map(|(&a, &t)| a as f64 / t.max(1) as f64).collect();
+        self.ladder = ladder;
+        best_solution
+    }
+}
+
+/// Returns the ladder with the gaps of the log temperatures rescaled
+/// towards balanced exchange rates: gaps of pairs exchanging more often
+/// than the mean widen, the others narrow, by the square root of the
+/// ratio of the rates. The coldest and the hottest temperatures do not change.
+fn adapt_ladder(ladder: &[f32], rates: &[f64]) -> Vec<f32> {
+    if ladder.len() < 3 {
+        return ladder.to_vec();
+    }
+    let mean_rate = rates.iter().sum::<f64>() / rates.len() as f64;
+    let gaps: Vec<f64> = ladder
+        .windows(2)
+        .zip(rates)
+        .map(|(pair, &rate)| (pair[1] as f64 / pair[0] as f64).ln() * ((rate + 0.01) / (mean_rate + 0.01)).sqrt())
+        .collect();
+    let span = (ladder[ladder.len() - 1] as f64 / ladder[0] as f64).ln();
+    let total: f64 = gaps.iter().sum();
+    if total <= 0.0 {
+        return ladder.to_vec();
+    }
+    let mut adapted = vec![ladder[0]];
+    let mut log_temperature = (ladder[0] as f64).ln();
+    for gap in gaps {
+        log_temperature += gap * span / total;
+        adapted.push(log_temperature.exp() as f32);
+    }
+    adapted
+}
+
+impl<'a> Solver for PTSolver<'a> {
+    fn solve(&mut self) -> Solution {
+        let initial_solution = self.generate_random_solution();
+        self.solve_parallel_tempering(initial_solution)
+    }
+    fn get_iter_count(&self) -> i32 {
+        self.iter_count
+    }
+    fn get_update_count(&self) -> i32 {
+        self.update_count
+    }
+    fn get_initial_solution(&self) -> Option<Solution> {
+        self.initial_solution.clone()
+    }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+}
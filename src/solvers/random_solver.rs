@@ -6,6 +6,7 @@ use rand::rngs::ThreadRng;
 use crate::qap_problem::QapProblem;
 use crate::solvers::solver::Solver;
 use crate::solution::Solution;
+use crate::trace::Trace;
 use crate::utils::*;
 
 /// Random Solver stores a ThreadRng object to reuse
@@ -14,6 +15,8 @@ pub struct RandomSolver<'a> {
     rng: ThreadRng,
     time_limit: u128,
     iter_count: i32,
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
 }
 
 impl<'a> RandomSolver<'a> {
@@ -27,7 +30,8 @@ impl<'a> RandomSolver<'a> {
             problem,
             rng,
             time_limit,
-            iter_count
+            iter_count,
+            trace: None,
         }
     }
 
@@ -59,6 +63,9 @@ impl<'a> Solver for RandomSolver<'a>{
             self.problem.matrix_a_ref(), 
             self.problem.matrix_b_ref()
         );
+        if let Some(trace) = &mut self.trace {
+            trace.update(1, best_score);
+        }
 
         let start = Instant::now();
         let mut elapsed: u128 = 0;
@@ -72,6 +79,9 @@ impl<'a> Solver for RandomSolver<'a>{
                 self.problem.matrix_a_ref(), 
                 self.problem.matrix_b_ref()
             );
+            if let Some(trace) = &mut self.trace {
+                trace.update(1, score);
+            }
 
             if score < best_score {
                 best_score = score;
@@ -97,4 +107,10 @@ impl<'a> Solver for RandomSolver<'a>{
     fn get_initial_solution(&self) -> Option<Solution> {
         None
     }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
 }
\ No newline at end of file
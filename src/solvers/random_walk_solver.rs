@@ -3,6 +3,7 @@ use rand::rngs::ThreadRng;
 use crate::qap_problem::QapProblem;
 use crate::solution::Solution;
 use crate::solvers::solver::Solver;
+use crate::trace::Trace;
 use crate::utils::*;
 use std::time::Instant;
 use rand::Rng;
@@ -13,6 +14,8 @@ pub struct RandomWalkSolver<'a> {
     rng: ThreadRng,
     iter_count: i32,
     time_limit: u128,
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
 }
 
 impl<'a> RandomWalkSolver<'a> {
@@ -32,6 +35,7 @@ impl<'a> RandomWalkSolver<'a> {
             rng,
             iter_count,
             time_limit,
+            trace: None,
         }
     }
 
@@ -49,6 +53,9 @@ impl<'a> RandomWalkSolver<'a> {
 
         current_solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
         let mut current_score = current_solution.get_eval();
+        if let Some(trace) = &mut self.trace {
+            trace.update(1, current_score);
+        }
 
         let mut best_score = current_score;
         let mut current_array: Vec<usize> = current_solution.get_solution_array();
@@ -81,6 +88,9 @@ impl<'a> RandomWalkSolver<'a> {
             // we perform a random move always
             current_solution.exchange_facilities(&pair);
             current_score = current_score + delta;
+            if let Some(trace) = &mut self.trace {
+                trace.update(1, current_score);
+            }
 
             if current_score < best_score {
                 // if the performed move cause the improvement
@@ -121,4 +131,10 @@ impl<'a> Solver for RandomWalkSolver<'a> {
     fn get_initial_solution(&self) -> Option<Solution> {
         None
     }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
 }
@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+
+use crate::acceptance::{AcceptanceCriterion, GreatDeluge, LateAcceptance, RecordToRecordTravel, ThresholdAccepting};
+use crate::cooling::TemperatureCalibration;
+use crate::qap_problem::QapProblem;
+use crate::solvers::acceptance_solver::AcceptanceSolver;
+use crate::solvers::first_improvement_solver::FirstImprovementLSSolver;
+use crate::solvers::gls_solver::{GLSSettings, GLSSolver};
+use crate::solvers::greedy_heuristic_solver::HeuristicSolver;
+use crate::solvers::greedy_ls_solver::GreedyLSSolver;
+use crate::solvers::parallel_tempering_solver::{PTSettings, PTSolver};
+use crate::solvers::random_solver::RandomSolver;
+use crate::solvers::random_walk_solver::RandomWalkSolver;
+use crate::solvers::simulated_annealing_solver::SASolver;
+use crate::solvers::solver::Solver;
+use crate::solvers::steepest_ls_solver::SteepestLSSolver;
+use crate::solvers::tabu_search_solver::TSSolver;
+use crate::solvers::vns_solver::{VNSSettings, VNSSolver, VNSVariant};
+
+/// Named solver parameters, e.g. {"tenure": 10, "k": 5}
+pub type Parameters = BTreeMap<String, f64>;
+
+/// Names of the solvers that can be built by `build_solver`
+pub const SOLVER_NAMES: [&str; 17] = [
+    "greedy",
+    "steepest",
+    "first-improvement",
+    "random",
+    "random-walk",
+    "heuristic",
+    "tabu",
+    "sa",
+    "great-deluge",
+    "threshold-accepting",
+    "rrt",
+    "lahc",
+    "pt",
+    "vns",
+    "gvns",
+    "skewed-vns",
+    "gls",
+];
+
+/// Returns the default parameters of a solver
+pub fn default_parameters(name: &str) -> Parameters {
+    let defaults: &[(&str, f64)] = match name {
+        "random" | "random-walk" | "heuristic" => &[("time_limit", 1000.0)],
+        "tabu" => &[
+            ("tenure", 20.0),
+            ("cn_ratio", 0.7),
+            ("k", 10.0),
+            ("lack_impr_iter", 100.0),
+        ],
+        "sa" => &[
+            ("temp_mul", 0.9),
+            ("iter_mul", 8.0),
+            ("l_div", 4.0),
+            ("initial_acceptance", 0.95),
+        ],
+        // iter_mul is the number of iterations without improvement, in neighbourhood sizes
+        "great-deluge" => &[("rain_speed", 0.0005), ("iter_mul", 20.0)],
+        "threshold-accepting" => &[("threshold", 0.05), ("decay", 0.9995), ("iter_mul", 20.0)],
+        "rrt" => &[("deviation", 0.005), ("iter_mul", 20.0)],
+        "lahc" => &[("history_length", 200.0), ("iter_mul", 20.0)],
+        "pt" => &[
+            ("n_replicas", 8.0),
+            ("l_div", 4.0),
+            ("max_no_improvement", 100.0),
+            ("n_threads", 1.0),
+        ],
+        "vns" => &[("k_max", 10.0), ("max_no_improvement", 100.0)],
+        // Every descent of general VNS ends with a full scan of the 3-cycles
+        "gvns" => &[("k_max", 10.0), ("max_no_improvement", 30.0)],
+        "skewed-vns" => &[("k_max", 10.0), ("max_no_improvement", 100.0), ("skew", 0.05)],
+        "gls" => &[("alpha", 0.05), ("max_no_improvement", 500.0)],
+        _ => &[],
+    };
+    defaults.iter().map(|&(key, value)| (key.to_string(), value)).collect()
+}
+
+/// Builds a solver by name. Missing parameters take default values.
+pub fn build_solver<'a>(
+    name: &str,
+    problem: &'a mut QapProblem,
+    parameters: &Parameters,
+) -> Result<Box<dyn Solver + 'a>, String> {
+    let mut all_parameters = default_parameters(name);
+    for (key, value) in parameters {
+        if !all_parameters.contains_key(key) {
+            return Err(format!("Unknown parameter {} of solver {}", key, name));
+        }
+        all_parameters.insert(key.clone(), *value);
+    }
+    let get = |key: &str| all_parameters[key];
+
+    let solver: Box<dyn Solver + 'a> = match name {
+        "greedy" => Box::new(GreedyLSSolver::new(problem)),
+        "steepest" => Box::new(SteepestLSSolver::new(problem)),
+        "first-improvement" => Box::new(FirstImprovementLSSolver::new(problem)),
+        "random" => Box::new(RandomSolver::new(problem, Some(get("time_limit") as u128))),
+        "random-walk" => Box::new(RandomWalkSolver::new(problem, Some(get("time_limit") as u128))),
+        "heuristic" => Box::new(HeuristicSolver::new(problem, Some(get("time_limit") as u128))),
+        "tabu" => Box::new(TSSolver::new(
+            problem,
+            get("tenure").round() as i32,
+            get("cn_ratio") as f32,
+            (get("k").round() as usize).max(1),
+            get("lack_impr_iter").round() as i32,
+        )),
+        "sa" => {
+            let mut sa_solver = SASolver::new(
+                problem,
+                get("temp_mul") as f32,
+                get("iter_mul").round() as usize,
+                (get("l_div").round() as i32).max(1),
+            );
+            sa_solver.calibrate_temperature(&TemperatureCalibration {
+                initial_acceptance: get("initial_acceptance"),
+                ..TemperatureCalibration::default()
+            });
+            Box::new(sa_solver)
+        }
+        "great-deluge" | "threshold-accepting" | "rrt" | "lahc" => {
+            let n = problem.get_n();
+            let max_no_improvement = (get("iter_mul") * (n * n.saturating_sub(1) / 2) as f64).round() as usize;
+            let criterion: Box<dyn AcceptanceCriterion> = match name {
+                "great-deluge" => Box::new(GreatDeluge::new(get("rain_speed"))),
+                "threshold-accepting" => Box::new(ThresholdAccepting::new(get("threshold"), get("decay"))),
+                "rrt" => Box::new(RecordToRecordTravel {
+                    deviation: get("deviation"),
+                }),
+                _ => Box::new(LateAcceptance::new(get("history_length").round() as usize)),
+            };
+            Box::new(AcceptanceSolver::new(problem, criterion, max_no_improvement))
+        }
+        "pt" => Box::new(PTSolver::new(
+            problem,
+            PTSettings {
+                n_replicas: (get("n_replicas").round() as usize).max(1),
+                l_div: (get("l_div").round() as usize).max(1),
+                max_no_improvement: get("max_no_improvement").round() as usize,
+                n_threads: (get("n_threads").round() as usize).max(1),
+                ..PTSettings::default()
+            },
+        )),
+        "vns" | "gvns" | "skewed-vns" => {
+            let variant = match name {
+                "vns" => VNSVariant::Basic,
+                "gvns" => VNSVariant::General,
+                _ => VNSVariant::Skewed,
+            };
+            Box::new(VNSSolver::new(
+                problem,
+                VNSSettings {
+                    variant,
+                    k_max: (get("k_max").round() as usize).max(1),
+                    max_no_improvement: get("max_no_improvement").round() as usize,
+                    skew: all_parameters.get("skew").copied().unwrap_or(0.0),
+                    ..VNSSettings::default()
+                },
+            ))
+        }
+        "gls" => Box::new(GLSSolver::new(
+            problem,
+            GLSSettings {
+                alpha: get("alpha"),
+                max_no_improvement: get("max_no_improvement").round() as usize,
+            },
+        )),
+        _ => return Err(format!("Unknown solver {}", name)),
+    };
+    Ok(solver)
+}
@@ -1,14 +1,21 @@
 use rand::rngs::ThreadRng;
+use serde_json::{json, Value};
 
+use crate::checkpoint::{solution_from_json, solution_to_json, StateSnapshot};
+use crate::cooling::{
+    metropolis_accept, temperature_for_acceptance, ChainStats, CoolingSchedule, GeometricCooling, StoppingRule, TemperatureCalibration,
+};
 use crate::qap_problem::QapProblem;
+use crate::neighborhood::SwapSampler;
 use crate::solution::Solution;
 use crate::solvers::solver::Solver;
+use crate::trace::Trace;
 use crate::utils::*;
-use rand::Rng;
 
 pub struct SASolver<'a> {
     problem: &'a QapProblem,
-    candidate_moves: Vec<[usize; 2]>,
+    /// Samples the swaps in a fixed cyclic order
+    sampler: SwapSampler,
     rng: ThreadRng,
     iter_count: i32,
     update_count: i32, // The number of times a solution is updated
@@ -16,40 +23,66 @@ pub struct SASolver<'a> {
     /// The initial solution
     initial_solution: Option<Solution>,
 
-    temp_mul: f32,
-    iter_mul: usize,
-    l_div: i32,
+    /// Gives the temperature of every Markov chain
+    cooling: Box<dyn CoolingSchedule>,
+    /// The number of moves tried at every temperature
+    chain_length: usize,
+    /// When the annealing stops
+    stopping_rule: StoppingRule,
+    /// Saves the annealing state periodically, if set
+    state_snapshot: Option<StateSnapshot>,
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
 }
 
 impl<'a> SASolver<'a> {
     // Constructor
     pub fn new(problem: &'a QapProblem, temp_mul: f32, iter_mul: usize, l_div: i32) -> SASolver<'a> {
-        // Vector of pairs (i, j), moves in order
-        let candidate_moves = generate_pairs(problem.get_n());
+        let sampler = SwapSampler::new(problem.get_n());
         let rng = rand::thread_rng();
         let iter_count: i32 = 0;
         let temperature: f32 = 0.95;
         let update_count: i32 = 0;
         let initial_solution = None;
 
-        let temp_mul = temp_mul;
-        let iter_mul = iter_mul;
-        let l_div = l_div;
+        // let the chain length be a fraction of the number of candidate moves, rounded down
+        let chain_length = (sampler.len() / l_div.max(1) as usize).max(1);
+        let stopping_rule = StoppingRule {
+            no_improvement_chains: iter_mul,
+            ..StoppingRule::default()
+        };
 
         SASolver {
             problem,
-            candidate_moves,
+            sampler,
             rng,
             iter_count,
             update_count,
             temperature,
             initial_solution,
-            temp_mul,
-            iter_mul,
-            l_div,
+            cooling: Box::new(GeometricCooling { alpha: temp_mul }),
+            chain_length,
+            stopping_rule,
+            state_snapshot: None,
+            trace: None,
         }
     }
 
+    /// Sets the cooling schedule, geometric with factor temp_mul by default
+    pub fn set_cooling_schedule(&mut self, cooling: Box<dyn CoolingSchedule>) {
+        self.cooling = cooling
+    }
+
+    /// Sets the number of moves tried at every temperature
+    pub fn set_chain_length(&mut self, chain_length: usize) {
+        self.chain_length = chain_length.max(1)
+    }
+
+    /// Sets when the annealing stops
+    pub fn set_stopping_rule(&mut self, stopping_rule: StoppingRule) {
+        self.stopping_rule = stopping_rule
+    }
+
     fn generate_random_solution(&mut self) -> Solution {
         let n = self.problem.get_n();
         let mut solution_array = vec![0; n];
@@ -58,83 +91,119 @@ impl<'a> SASolver<'a> {
         Solution::new(solution_array)
     }
 
+    /// Sets the initial temperature accepting 95% of the worsening moves
     pub fn compute_initial_temperature(&mut self) {
-        // generate random 1,000 solutions
-        // for each solution generate random neighbor and compute delta
-        // compute average delta
+        self.calibrate_temperature(&TemperatureCalibration::default());
+    }
 
+    /// Sets the initial temperature to accept the target ratio of the
+    /// worsening moves. With a target final ratio, the final temperature
+    /// becomes the minimum temperature of the stopping rule and the
+    /// factor of the geometric cooling reaching it after `n_levels`
+    /// chains is returned. The cooling schedule is not changed.
+    pub fn calibrate_temperature(&mut self, calibration: &TemperatureCalibration) -> Option<f32> {
+        // generate random solutions
+        // for each solution generate random neighbor and compute delta
         let mut deltas = Vec::new();
-        for _ in 0..1000 {
+        for _ in 0..calibration.n_samples {
             let mut solution = self.generate_random_solution();
             solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
-            let pair = self.candidate_moves[self.rng.gen_range(0..self.candidate_moves.len())];
+            let Some(pair) = self.sampler.random_move(&mut self.rng) else {
+                break;
+            };
             let delta = solution.calculate_delta(
                 self.problem.matrix_a_ref(),
                 self.problem.matrix_b_ref(),
                 &pair
             );
-            deltas.push(delta.abs());
+            deltas.push(delta);
         }
-        let sum: i128 = deltas.iter().map(|&delta| delta as i128).sum();
-        let avg = sum as f32 / deltas.len() as f32;
+        self.temperature = temperature_for_acceptance(&deltas, calibration.initial_acceptance);
 
-        // target initial probability = 0.95
-        let prob: f32 = 0.95;
-        // take ln from prob
-        let ln_prob: f32 = prob.ln();
-        // compute initial temperature
-        let temperature: f32 = -avg / ln_prob;
-        self.temperature = temperature;
+        let final_acceptance = calibration.final_acceptance?;
+        let final_temperature = temperature_for_acceptance(&deltas, final_acceptance).min(self.temperature);
+        self.stopping_rule.min_temperature = final_temperature;
+        Some((final_temperature / self.temperature).powf(1.0 / calibration.n_levels.max(1) as f32))
+    }
+
+    /// Returns the initial temperature
+    pub fn get_temperature(&self) -> f32 {
+        self.temperature
     }
 
     fn solve_simulated_annealing(&mut self, mut initial_solution: Solution) -> Solution {
-        let mut p = 0; // holds the iterations without improvement
-        // let mut k = 0; // holds the number of iterations through L
         initial_solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
         self.initial_solution = Some(Solution::new(initial_solution.get_solution_array()));
         self.initial_solution
             .as_mut()
             .unwrap()
             .set_eval(initial_solution.get_eval());
-        let mut current_solution = initial_solution.clone();
+        let current_solution = initial_solution.clone();
         // current_solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
-        // let l be a quarter of the number of candidate moves, rounded down
-        let l = self.candidate_moves.len() / self.l_div as usize;
         // draw a random number that indicates which candidate we take
-        let mut j = self.rng.gen_range(0..self.candidate_moves.len());
+        self.sampler.restart(&mut self.rng);
 
-        let mut local_temp = self.temperature;
-        // let mut current_score = current_solution.get_eval();
+        self.iter_count = 0;
+        self.update_count = 0;
+        self.cooling.reset();
 
-        // max number of iterations without improvement
-        let max_iter = l * self.iter_mul;
+        // A single facility has no swaps to anneal
+        if self.sampler.is_empty() {
+            return current_solution;
+        }
+        let best_solution = current_solution.clone();
+        self.anneal(current_solution, best_solution, 0, self.temperature, 0)
+    }
 
-        self.iter_count = 0;
+    /// The main loop of the annealing, starting from the given state
+    /// and the position of the sampler: `p` the number of
+    /// iterations without improvement, `local_temp` the temperature and
+    /// `level` the number of completed chains. Returns the best visited
+    /// solution, evaluated incrementally.
+    fn anneal(
+        &mut self,
+        mut current_solution: Solution,
+        mut best_solution: Solution,
+        mut p: usize,
+        mut local_temp: f32,
+        mut level: usize,
+    ) -> Solution {
+        let l = self.chain_length;
+        let mut current_score = current_solution.get_eval();
+        if let Some(trace) = &mut self.trace {
+            trace.update(1, current_score);
+        }
 
         loop {
+            let mut accepted = 0;
             for _ in 0..l {
-                let pair = self.candidate_moves[j];
-                let delta = current_solution.calculate_delta(
+                let (pair, delta) = self.sampler.next_move(
+                    &current_solution,
                     self.problem.matrix_a_ref(),
                     self.problem.matrix_b_ref(),
-                    &pair
                 );
                 if delta < 0 {
                     current_solution.exchange_facilities(&pair);
-                    // current_score += delta;
+                    current_score += delta;
                     self.update_count += 1;
+                    accepted += 1;
                     p = 0;
+                    if current_score < best_solution.get_eval() {
+                        best_solution.solution_array.clone_from(&current_solution.solution_array);
+                        best_solution.set_eval(current_score);
+                    }
                 } else {
                     p += 1;
-                    let q = (-delta as f32 / local_temp).exp();
-                    let r = self.rng.gen_range(0.0..1.0);
-                    if q > r {
+                    if metropolis_accept(delta, local_temp, &mut self.rng) {
                         current_solution.exchange_facilities(&pair);
                         self.update_count += 1;
-                        // current_score += delta;
+                        accepted += 1;
+                        current_score += delta;
                     }
                 }
-                j = (j + 1) % self.candidate_moves.len();
+                if let Some(trace) = &mut self.trace {
+                    trace.update(1, current_score);
+                }
                 self.iter_count += 1;
 
                 // print status every 10000 iterations
@@ -150,22 +219,43 @@ impl<'a> SASolver<'a> {
             }
             
 
-            // k += 1;
-            local_temp *= self.temp_mul;
+            level += 1;
+            let stats = ChainStats {
+                level,
+                initial_temperature: self.temperature,
+                acceptance_rate: accepted as f64 / l as f64,
+            };
+            local_temp = self.cooling.next_temperature(local_temp, &stats);
 
-            if local_temp < 0.01 && p >= max_iter{
+            if self.stopping_rule.should_stop(local_temp, level, self.iter_count as usize, p, l) {
                 break;
             }
+
+            // Save the state between temperature levels
+            if let Some(snapshot) = &self.state_snapshot {
+                current_solution.set_eval(current_score);
+                snapshot.save_if_due(self.iter_count, || {
+                    json!({
+                        "current_solution": solution_to_json(&current_solution),
+                        "best_solution": solution_to_json(&best_solution),
+                        "initial_solution": self.initial_solution.as_ref().map(solution_to_json),
+                        "j": self.sampler.position(),
+                        "p": p,
+                        "local_temp": local_temp,
+                        "level": level,
+                        "iter_count": self.iter_count,
+                        "update_count": self.update_count,
+                    })
+                });
+            }
         }
-        current_solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
-        current_solution
+        best_solution
     }
 
     
 }
 
 impl<'a> Solver for SASolver<'a> {
-    // Just greedy
     fn solve(&mut self) -> Solution {
         let initial_solution: Solution = self.generate_random_solution();
         self.solve_simulated_annealing(initial_solution)
@@ -174,9 +264,33 @@ impl<'a> Solver for SASolver<'a> {
         self.iter_count
     }
     fn get_update_count(&self) -> i32 {
-        0
+        self.update_count
     }
     fn get_initial_solution(&self) -> Option<Solution> {
         self.initial_solution.clone()
     }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+    fn set_state_snapshot(&mut self, snapshot: Option<StateSnapshot>) {
+        self.state_snapshot = snapshot
+    }
+    fn resume(&mut self, state: &Value) -> Option<Solution> {
+        let current_solution = solution_from_json(state.get("current_solution")?)?;
+        let j = state.get("j")?.as_u64()? as usize;
+        if current_solution.solution_array.len() != self.problem.get_n() || !self.sampler.set_position(j) {
+            return None;
+        }
+        self.initial_solution = state.get("initial_solution").and_then(solution_from_json);
+        let best_solution = state.get("best_solution").and_then(solution_from_json)?;
+        let p = state.get("p")?.as_u64()? as usize;
+        let local_temp = state.get("local_temp")?.as_f64()? as f32;
+        let level = state.get("level").and_then(Value::as_u64).unwrap_or(0) as usize;
+        self.iter_count = state.get("iter_count")?.as_i64()? as i32;
+        self.update_count = state.get("update_count")?.as_i64()? as i32;
+        Some(self.anneal(current_solution, best_solution, p, local_temp, level))
+    }
 }
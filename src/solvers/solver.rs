@@ -1,4 +1,8 @@
+use serde_json::Value;
+
+use crate::checkpoint::StateSnapshot;
 use crate::solution::Solution;
+use crate::trace::Trace;
 
 /// Trait for all solvers, for now there is only the method
 /// solve(), but we may think what should be added later on
@@ -7,4 +11,18 @@ pub trait Solver {
     fn get_iter_count(&self) -> i32;
     fn get_update_count(&self) -> i32;
     fn get_initial_solution(&self) -> Option<Solution>;
-}
\ No newline at end of file
+    /// Enables saving the solver state periodically during solve().
+    /// Solvers which cannot be resumed ignore it.
+    fn set_state_snapshot(&mut self, _snapshot: Option<StateSnapshot>) {}
+    /// Continues an interrupted run from a saved state. Returns None
+    /// if the solver cannot be resumed or the state is invalid.
+    fn resume(&mut self, _state: &Value) -> Option<Solution> {
+        None
+    }
+    /// Enables recording the best-so-far trace during solve()
+    fn set_trace(&mut self, _trace: Option<Trace>) {}
+    /// Returns the recorded trace and stops recording
+    fn take_trace(&mut self) -> Option<Trace> {
+        None
+    }
+}
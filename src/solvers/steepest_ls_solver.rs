@@ -1,41 +1,78 @@
 use rand::rngs::ThreadRng;
 
+use crate::neighborhood::{Move, Neighborhood, SwapNeighborhood};
+use crate::parallel::parallel_min;
 use crate::qap_problem::QapProblem;
 use crate::solution::Solution;
 use crate::solvers::solver::Solver;
+use crate::trace::Trace;
 use crate::utils::*;
 
 pub struct SteepestLSSolver<'a> {
     problem: &'a QapProblem,
-    candidate_moves: Vec<[usize; 2]>,
-    two_candidates_moves: Vec<Vec<[usize; 2]>>,
+    /// The neighbourhood searched in every step, swaps by default
+    neighborhood: Box<dyn Neighborhood>,
+    /// The moves of the neighbourhood, in its order
+    moves: Vec<Move>,
     rng: ThreadRng,
     iter_count: i32,   // The number of times the LS loop is ran
     update_count: i32, // The number of times a solution is updated
     initial_solution: Option<Solution>,
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
+    /// The number of threads evaluating the neighbourhood
+    n_threads: usize,
 }
 
 impl<'a> SteepestLSSolver<'a> {
     // Constructor
     pub fn new(problem: &'a QapProblem) -> SteepestLSSolver<'a> {
-        // Vector of pairs (i, j), moves in order
-        let candidate_moves = generate_pairs(problem.get_n());
-        let two_candidates_moves = generate_two_pairs(problem.get_n());
         let rng = rand::thread_rng();
         let iter_count: i32 = 0;
         let update_count: i32 = 0;
         let initial_solution = None;
         SteepestLSSolver {
             problem,
-            candidate_moves,
-            two_candidates_moves,
+            neighborhood: Box::new(SwapNeighborhood),
+            moves: SwapNeighborhood.moves(problem.get_n()).collect(),
             rng,
             iter_count,
             update_count,
-            initial_solution
+            initial_solution,
+            trace: None,
+            n_threads: 1,
         }
     }
 
+    /// Sets the neighbourhood searched in every step
+    pub fn set_neighborhood(&mut self, neighborhood: Box<dyn Neighborhood>) {
+        self.moves = neighborhood.moves(self.problem.get_n()).collect();
+        self.neighborhood = neighborhood
+    }
+
+    /// Sets the number of threads evaluating the neighbourhood. Moves
+    /// are split into contiguous ranges and ties are broken by the order
+    /// of the moves, so the descent does not depend on the number of threads.
+    pub fn set_n_threads(&mut self, n_threads: usize) {
+        self.n_threads = n_threads.max(1)
+    }
+
+    /// Returns the best improving move and its delta, the first
+    /// one in the order of the neighbourhood in case of ties
+    fn find_best_move(&self, solution: &Solution) -> Option<(Move, i32)> {
+        let (matrix_a, matrix_b) = (self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+        let (neighborhood, moves) = (&*self.neighborhood, &self.moves);
+        let best = parallel_min(moves.len(), self.n_threads, |start, end| {
+            moves[start..end]
+                .iter()
+                .enumerate()
+                .map(|(offset, mv)| (start + offset, neighborhood.delta(solution, matrix_a, matrix_b, mv)))
+                .filter(|&(_, delta)| delta < 0)
+                .min_by_key(|&(_, delta)| delta)
+        });
+        best.map(|(index, delta)| (moves[index], delta))
+    }
+
     fn generate_random_solution(&mut self) -> Solution {
         let n = self.problem.get_n();
         let mut solution_array = vec![0; n];
@@ -44,75 +81,36 @@ impl<'a> SteepestLSSolver<'a> {
         Solution::new(solution_array)
     }
 
-    fn solve_steepest(&mut self, mut initial_solution: Solution) -> Solution {
+    /// Runs the steepest descent from the initial solution
+    /// and returns the local optimum
+    pub fn solve_steepest(&mut self, mut initial_solution: Solution) -> Solution {
         initial_solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
         self.initial_solution = Some(Solution::new(initial_solution.get_solution_array()));
         self.initial_solution.as_mut().unwrap().set_eval(initial_solution.get_eval());
         let mut current_solution = initial_solution;
-        // println!("Current solution: {}", current_solution);
+        if let Some(trace) = &mut self.trace {
+            trace.update(1, current_solution.get_eval());
+        }
 
-        let num_candidates = self.candidate_moves.len();
+        let n = self.problem.get_n();
+        let num_candidates = self.neighborhood.size(n);
 
-        // let mut i = 1;
         let mut iter_count = 0;
         self.update_count = 0;
         loop {
-            let mut best_delta: i32 = num_traits::zero();
-            // let mut best_pair: [usize; 2] = [0,0];
-
-            let mut best_pairs  = vec![];
-    
-            for &pair in &self.candidate_moves {
-                let delta: i32 = current_solution.calculate_delta(
-                    self.problem.matrix_a_ref(),
-                    self.problem.matrix_b_ref(),
-                    &pair,
-                );
-    
-                // println!("Delta for pair {:?}: {}", pair, delta);
-    
-                if delta < best_delta {
-                    best_delta = delta;
-                    best_pairs = vec![pair];
-                }
+            let best_move = self.find_best_move(&current_solution);
+            let best_delta = best_move.map_or(0, |(_, delta)| delta);
+            if let Some(trace) = &mut self.trace {
+                trace.update(num_candidates as u64, current_solution.get_eval() + best_delta);
             }
 
-            // exchange 2 pairs in the solution
-            // for i in 0..num_candidates{
-            //     for j in i+1..num_candidates{
-            //         let pair1 = self.candidate_moves[i];
-            //         let pair2 = self.candidate_moves[j];
-            //         best_pairs = vec![pair1, pair2];
-            //         let delta: i32 = current_solution.calculate_n_deltas(
-            //             self.problem.matrix_a_ref(), 
-            //             self.problem.matrix_b_ref(),
-            //             &best_pairs);
-            //         if delta < best_delta {
-            //             best_delta = delta;
-            //             best_pairs = vec![pair1, pair2];
-            //         }
-            //     }
-            // }
-            // for pairs in &self.two_candidates_moves {
-            //     let delta: i32 = current_solution.calculate_n_deltas(
-            //         self.problem.matrix_a_ref(),
-            //         self.problem.matrix_b_ref(),
-            //         &pairs,
-            //     );
-            //     if delta < best_delta {
-            //         best_delta = delta;
-            //         best_pairs = pairs.to_vec();
-            //     }
-            // }
-            
-            if best_delta < num_traits::zero() {
-                current_solution.exchange_n_facilities(&best_pairs);
-                self.update_count = self.update_count + 1;
-                // println!("Best pair: {:?}, Delta: {}; at epoch {}", best_pairs, best_delta, iter_count);
-            } else {
-                break;
+            match best_move {
+                Some((mv, _)) => {
+                    self.neighborhood.apply(&mut current_solution, &mv, best_delta);
+                    self.update_count = self.update_count + 1;
+                }
+                None => break,
             }
-            // i = i + 1;
             iter_count = iter_count + 1;
         }
         self.iter_count = iter_count;
@@ -139,4 +137,10 @@ impl<'a> Solver for SteepestLSSolver<'a> {
     fn get_initial_solution(&self) -> Option<Solution> {
         self.initial_solution.clone()
     }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
 }
\ No newline at end of file
@@ -1,9 +1,13 @@
 use rand::rngs::ThreadRng;
+use serde_json::{json, Value};
 
 use crate::candidate_move::CandidateMove;
+use crate::checkpoint::{solution_from_json, solution_to_json, StateSnapshot};
+use crate::parallel::parallel_map;
 use crate::qap_problem::QapProblem;
 use crate::solution::Solution;
 use crate::solvers::solver::Solver;
+use crate::trace::Trace;
 use crate::utils::generate_pairs;
 use crate::utils::permute_array;
 use rand::Rng;
@@ -38,6 +42,12 @@ pub struct TSSolver<'a> {
     k: usize,
     // Stopping criterion
     lack_improvement_iter: i32,
+    /// Saves the search state periodically, if set
+    state_snapshot: Option<StateSnapshot>,
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
+    /// The number of threads evaluating the candidate moves
+    n_threads: usize,
 }
 
 fn create_tabu_list(n: usize) -> Vec<Vec<i32>> {
@@ -86,9 +96,19 @@ impl<'a> TSSolver<'a> {
             cn_ratio,
             k,
             lack_improvement_iter,
+            state_snapshot: None,
+            trace: None,
+            n_threads: 1,
         }
     }
 
+    /// Sets the number of threads evaluating the neighbourhood moves.
+    /// The deltas keep the order of the moves, so the candidate list
+    /// does not depend on the number of threads.
+    pub fn set_n_threads(&mut self, n_threads: usize) {
+        self.n_threads = n_threads.max(1)
+    }
+
     /// Constructs candidate list by evaluating a subset of moves from the neighborhood
     /// and selecting k of them. The number of evaluated moves is equal to the
     /// size of the neighborhood times the cn_ratio (a float between 0 and 1).
@@ -97,22 +117,16 @@ impl<'a> TSSolver<'a> {
         self.candidate_list.clear();
         // Calculate how many nieghborhood moves should be checked
         let list_size = (self.neighborhood_moves.len() as f32 * self.cn_ratio) as usize;
-        // Initialize loop counter
-        let mut i: usize = 0;
         // Randomize the order of neighboring moves
         permute_array(&mut self.rng, &mut self.neighborhood_moves);
-        while i < list_size {
-            let pair = &self.neighborhood_moves[i];
-            let delta: i32 = current_solution.calculate_delta(
-                self.problem.matrix_a_ref(),
-                self.problem.matrix_b_ref(),
-                pair,
-            );
-            // Create a move from the pair and delta
-            let candidate_move = CandidateMove::new(*pair, delta);
-            // Push the move to the list
-            self.candidate_list.push(candidate_move);
-            i += 1;
+        let (matrix_a, matrix_b) = (self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+        // Evaluate the moves, split across the threads
+        let candidate_moves = parallel_map(&self.neighborhood_moves[..list_size], self.n_threads, |pair| {
+            CandidateMove::new(*pair, current_solution.calculate_delta(matrix_a, matrix_b, pair))
+        });
+        self.candidate_list.extend(candidate_moves);
+        if let Some(trace) = &mut self.trace {
+            trace.update(list_size as u64, current_solution.get_eval());
         }
         // Sort the candidate moves by descending delta
         self.candidate_list.sort_by_key(|c| c.get_delta());
@@ -135,6 +149,9 @@ impl<'a> TSSolver<'a> {
                 &candidate_move.pair,
             );
         }
+        if let Some(trace) = &mut self.trace {
+            trace.update(self.candidate_list.len() as u64, current_solution.get_eval());
+        }
         // Sort by descending delta
         self.candidate_list.sort_by_key(|c| -c.get_delta());
     }
@@ -238,13 +255,31 @@ impl<'a> TSSolver<'a> {
             .unwrap()
             .set_eval(initial_solution.get_eval());
         // Assign current colution to initial solution
-        let mut current_solution = initial_solution.clone();
+        let current_solution = initial_solution.clone();
         // Assign best solution to initial solution
-        let mut best_solution = initial_solution.clone();
+        let best_solution = initial_solution.clone();
 
-        // Initialize the counter of iterations without an improvement
-        let mut lack_improvement_iter = 0;
+        // Reset the counters of the previous run
+        self.iter_count = 0;
+        self.update_count = 0;
+        // Candidate deltas and tabu list refer to the previous run
+        self.candidate_list.clear();
+        self.tabu_list = create_tabu_list(self.problem.get_n());
+        if let Some(trace) = &mut self.trace {
+            trace.update(1, initial_solution.get_eval());
+        }
 
+        self.search(current_solution, best_solution, 0)
+    }
+
+    /// The main loop of the tabu search, starting from the given state.
+    /// `lack_improvement_iter` is the number of iterations without an improvement.
+    fn search(
+        &mut self,
+        mut current_solution: Solution,
+        mut best_solution: Solution,
+        mut lack_improvement_iter: i32,
+    ) -> Solution {
         while lack_improvement_iter < self.lack_improvement_iter {
             self.improving_delta = current_solution.get_eval() - best_solution.get_eval();
             let selected_move = self.select_best_move(&current_solution);
@@ -256,6 +291,9 @@ impl<'a> TSSolver<'a> {
 
             // Update the evaluation of the current solution
             current_solution.set_eval(current_solution.get_eval() + selected_move.delta);
+            if let Some(trace) = &mut self.trace {
+                trace.update(0, current_solution.get_eval());
+            }
 
             if current_solution.get_eval() < best_solution.get_eval() {
                 lack_improvement_iter = 0;
@@ -270,9 +308,28 @@ impl<'a> TSSolver<'a> {
             }
 
             self.iter_count += 1;
+
+            if let Some(snapshot) = &self.state_snapshot {
+                snapshot.save_if_due(self.iter_count, || {
+                    self.get_state(&current_solution, &best_solution, lack_improvement_iter)
+                });
+            }
         }
         best_solution
     }
+
+    /// Returns the search state as json
+    fn get_state(&self, current_solution: &Solution, best_solution: &Solution, lack_improvement_iter: i32) -> Value {
+        json!({
+            "current_solution": solution_to_json(current_solution),
+            "best_solution": solution_to_json(best_solution),
+            "initial_solution": self.initial_solution.as_ref().map(solution_to_json),
+            "tabu_list": self.tabu_list,
+            "iter_count": self.iter_count,
+            "update_count": self.update_count,
+            "lack_improvement_iter": lack_improvement_iter,
+        })
+    }
 }
 
 impl<'a> Solver for TSSolver<'a> {
@@ -290,4 +347,30 @@ impl<'a> Solver for TSSolver<'a> {
     fn get_initial_solution(&self) -> Option<Solution> {
         self.initial_solution.clone()
     }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+    fn set_state_snapshot(&mut self, snapshot: Option<StateSnapshot>) {
+        self.state_snapshot = snapshot
+    }
+    fn resume(&mut self, state: &Value) -> Option<Solution> {
+        let current_solution = solution_from_json(state.get("current_solution")?)?;
+        let best_solution = solution_from_json(state.get("best_solution")?)?;
+        let tabu_list: Vec<Vec<i32>> = serde_json::from_value(state.get("tabu_list")?.clone()).ok()?;
+        if current_solution.solution_array.len() != self.problem.get_n()
+            || tabu_list.len() != self.tabu_list.len()
+        {
+            return None;
+        }
+        self.initial_solution = state.get("initial_solution").and_then(solution_from_json);
+        self.tabu_list = tabu_list;
+        self.iter_count = state.get("iter_count")?.as_i64()? as i32;
+        self.update_count = state.get("update_count")?.as_i64()? as i32;
+        let lack_improvement_iter = state.get("lack_improvement_iter")?.as_i64()? as i32;
+        self.candidate_list.clear();
+        Some(self.search(current_solution, best_solution, lack_improvement_iter))
+    }
 }
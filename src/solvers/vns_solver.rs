@@ -0,0 +1,245 @@
+use rand::rngs::ThreadRng;
+
+use crate::neighborhood::{CycleNeighborhood, Neighborhood, SwapNeighborhood};
+use crate::qap_problem::QapProblem;
+use crate::solution::Solution;
+use crate::solvers::greedy_ls_solver::GreedyLSSolver;
+use crate::solvers::solver::Solver;
+use crate::solvers::steepest_ls_solver::SteepestLSSolver;
+use crate::trace::Trace;
+use crate::utils::*;
+
+/// Variant of variable neighbourhood search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VNSVariant {
+    /// Shaking followed by a swap descent, moves only to better solutions
+    Basic,
+    /// Shaking followed by variable neighbourhood descent over swaps and 3-cycles
+    General,
+    /// Basic VNS which also moves to slightly worse solutions far from the current one
+    Skewed,
+}
+
+impl VNSVariant {
+    /// All variants
+    pub const ALL: [VNSVariant; 3] = [VNSVariant::Basic, VNSVariant::General, VNSVariant::Skewed];
+
+    /// Returns the name of the variant
+    pub fn name(&self) -> &'static str {
+        match self {
+            VNSVariant::Basic => "basic",
+            VNSVariant::General => "general",
+            VNSVariant::Skewed => "skewed",
+        }
+    }
+
+    /// Returns the variant of a name
+    pub fn from_name(name: &str) -> Option<VNSVariant> {
+        VNSVariant::ALL.into_iter().find(|variant| variant.name() == name)
+    }
+}
+
+/// The descent of the local search stage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VNSDescent {
+    Steepest,
+    Greedy,
+}
+
+/// Settings of variable neighbourhood search
+#[derive(Debug, Clone, Copy)]
+pub struct VNSSettings {
+    pub variant: VNSVariant,
+    /// The descent in the swap neighbourhood
+    pub descent: VNSDescent,
+    /// The largest shaking neighbourhood: k random swaps
+    pub k_max: usize,
+    /// Stopping criterion: shakes without improvement of the best solution
+    pub max_no_improvement: usize,
+    /// Skewed VNS moves to a solution if its evaluation is below the current
+    /// one plus `skew` times the current evaluation times the fraction of
+    /// locations where they differ
+    pub skew: f64,
+}
+
+impl Default for VNSSettings {
+    fn default() -> Self {
+        VNSSettings {
+            variant: VNSVariant::Basic,
+            descent: VNSDescent::Steepest,
+            k_max: 10,
+            max_no_improvement: 100,
+            skew: 0.05,
+        }
+    }
+}
+
+/// Variable neighbourhood search: shakes the current solution with k
+/// random swaps and descends from it. If the local optimum is accepted
+/// the search restarts from k = 1, otherwise k grows up to k_max.
+pub struct VNSSolver<'a> {
+    problem: &'a QapProblem,
+    rng: ThreadRng,
+    /// The descents of the local search stage
+    steepest: SteepestLSSolver<'a>,
+    greedy: GreedyLSSolver<'a>,
+    iter_count: i32, // The number of evaluated moves
+    update_count: i32, // The number of times a solution is updated
+    initial_solution: Option<Solution>,
+    settings: VNSSettings,
+    /// Records the best-so-far trace, if set
+    trace: Option<Trace>,
+}
+
+impl<'a> VNSSolver<'a> {
+    // Constructor
+    pub fn new(problem: &'a QapProblem, settings: VNSSettings) -> VNSSolver<'a> {
+        VNSSolver {
+            problem,
+            rng: rand::thread_rng(),
+            steepest: SteepestLSSolver::new(problem),
+            greedy: GreedyLSSolver::new(problem),
+            iter_count: 0,
+            update_count: 0,
+            initial_solution: None,
+            settings,
+            trace: None,
+        }
+    }
+
+    fn generate_random_solution(&mut self) -> Solution {
+        let n = self.problem.get_n();
+        let mut solution_array = vec![0; n];
+        arange(&mut solution_array, 0, 1);
+        permute_array(&mut self.rng, &mut solution_array);
+        Solution::new(solution_array)
+    }
+
+    /// Returns the solution changed by k random swaps, unchanged if
+    /// there are no swaps (n < 2)
+    fn shake(&mut self, solution: &Solution, k: usize) -> Solution {
+        let n = self.problem.get_n();
+        let mut shaken = solution.clone();
+        if SwapNeighborhood.size(n) == 0 {
+            return shaken;
+        }
+        for _ in 0..k {
+            SwapNeighborhood.random_move(&mut self.rng, n).apply(&mut shaken);
+        }
+        shaken
+    }
+
+    /// Runs the swap descent and, for general VNS, the variable neighbourhood
+    /// descent: at a swap local optimum the best 3-cycle is applied if it
+    /// improves the solution, followed by the swap descent again. Returns
+    /// the local optimum of both neighbourhoods. The descents record
+    /// their evaluated moves in the trace of the search.
+    fn local_search(&mut self, solution: Solution) -> Solution {
+        let (matrix_a, matrix_b) = (self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+        let n = self.problem.get_n();
+        let mut current_solution = solution;
+        loop {
+            current_solution = match self.settings.descent {
+                VNSDescent::Steepest => {
+                    self.steepest.set_trace(self.trace.take());
+                    let local_optimum = self.steepest.solve_steepest(current_solution);
+                    self.trace = self.steepest.take_trace();
+                    // Every step and the final one scan the whole neighbourhood
+                    self.iter_count += (self.steepest.get_iter_count() + 1) * SwapNeighborhood.size(n) as i32;
+                    local_optimum
+                }
+                VNSDescent::Greedy => {
+                    self.greedy.set_trace(self.trace.take());
+                    let local_optimum = self.greedy.solve_greedy(current_solution);
+                    self.trace = self.greedy.take_trace();
+                    // The last evaluated move is not counted by the descent
+                    self.iter_count += self.greedy.get_iter_count() + 1;
+                    local_optimum
+                }
+            };
+            if self.settings.variant != VNSVariant::General {
+                return current_solution;
+            }
+            let best_cycle = CycleNeighborhood
+                .moves(n)
+                .map(|mv| (CycleNeighborhood.delta(&current_solution, matrix_a, matrix_b, &mv), mv))
+                .min_by_key(|&(delta, _)| delta);
+            self.iter_count += CycleNeighborhood.size(n) as i32;
+            if let Some(trace) = &mut self.trace {
+                let best_delta = best_cycle.map_or(0, |(delta, _)| delta.min(0));
+                trace.update(CycleNeighborhood.size(n) as u64, current_solution.get_eval() + best_delta);
+            }
+            match best_cycle {
+                Some((delta, mv)) if delta < 0 => CycleNeighborhood.apply(&mut current_solution, &mv, delta),
+                _ => return current_solution,
+            }
+        }
+    }
+
+    /// Returns true if the search moves from the current solution to the candidate
+    fn accept(&self, current_solution: &Solution, candidate: &Solution) -> bool {
+        let (current, eval) = (current_solution.get_eval() as f64, candidate.get_eval() as f64);
+        match self.settings.variant {
+            VNSVariant::Skewed => {
+                let distance = current_solution.hamming_distance(candidate) as f64 / self.problem.get_n() as f64;
+                candidate.solution_array != current_solution.solution_array
+                    && eval < current + self.settings.skew * current.abs() * distance
+            }
+            _ => eval < current,
+        }
+    }
+
+    /// Runs the search from the initial solution and returns the best solution
+    pub fn solve_vns(&mut self, mut initial_solution: Solution) -> Solution {
+        initial_solution.evaluate(self.problem.matrix_a_ref(), self.problem.matrix_b_ref());
+        self.initial_solution = Some(initial_solution.clone());
+        self.iter_count = 0;
+        self.update_count = 0;
+
+        let mut current_solution = self.local_search(initial_solution);
+        let mut best_solution = current_solution.clone();
+        let k_max = self.settings.k_max.clamp(1, (self.problem.get_n() / 2).max(1));
+        // the number of shakes without improvement of the best solution
+        let mut p = 0;
+        let mut k = 1;
+        while p < self.settings.max_no_improvement {
+            let shaken = self.shake(&current_solution, k);
+            let local_optimum = self.local_search(shaken);
+            p += 1;
+            if local_optimum.get_eval() < best_solution.get_eval() {
+                best_solution = local_optimum.clone();
+                p = 0;
+            }
+            if self.accept(&current_solution, &local_optimum) {
+                current_solution = local_optimum;
+                self.update_count += 1;
+                k = 1;
+            } else {
+                k = k % k_max + 1;
+            }
+        }
+        best_solution
+    }
+}
+
+impl<'a> Solver for VNSSolver<'a> {
+    fn solve(&mut self) -> Solution {
+        let initial_solution = self.generate_random_solution();
+        self.solve_vns(initial_solution)
+    }
+    fn get_iter_count(&self) -> i32 {
+        self.iter_count
+    }
+    fn get_update_count(&self) -> i32 {
+        self.update_count
+    }
+    fn get_initial_solution(&self) -> Option<Solution> {
+        self.initial_solution.clone()
+    }
+    fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+    fn take_trace(&mut self) -> Option<Trace> {
+        self.trace.take()
+    }
+}
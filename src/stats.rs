@@ -0,0 +1,666 @@
+use crate::experiment::ExperimentResults;
+
+use csv::Writer;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::f64::consts::PI;
+
+/// Final evaluations of all runs of one solver on one instance
+#[derive(Debug, Clone)]
+pub struct ResultSet {
+    pub instance: String,
+    pub solver: String,
+    pub values: Vec<f64>,
+}
+
+impl ResultSet {
+    /// Constructor of the result set from the results of an experiment
+    pub fn new(instance: &str, solver: &str, results: &ExperimentResults) -> ResultSet {
+        let values = results.final_evaluations().iter().map(|&e| e as f64).collect();
+        ResultSet {
+            instance: instance.to_string(),
+            solver: solver.to_string(),
+            values,
+        }
+    }
+
+    /// Returns the mean of the values
+    pub fn mean(&self) -> f64 {
+        self.values.iter().sum::<f64>() / self.values.len() as f64
+    }
+}
+
+/// The outcome of a statistical test
+#[derive(Debug, Clone, Copy)]
+pub struct TestResult {
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+/// The result of comparing two solvers with a pairwise test
+#[derive(Debug, Clone)]
+pub struct PairwiseComparison {
+    /// The instance the runs come from, None if the test is over instances
+    pub instance: Option<String>,
+    pub solver_a: String,
+    pub solver_b: String,
+    /// The name of the test
+    pub test: &'static str,
+    pub statistic: f64,
+    pub p_value: f64,
+    /// The p-value after Holm (or Nemenyi) correction
+    pub adjusted_p_value: f64,
+    pub significant: bool,
+}
+
+/// The result of the Friedman test over instances with the Nemenyi post-hoc
+#[derive(Debug, Clone)]
+pub struct FriedmanResult {
+    /// Solvers in the order of the columns of the test
+    pub solvers: Vec<String>,
+    /// Instances (blocks) used in the test
+    pub instances: Vec<String>,
+    /// Average rank of each solver (1 is the best)
+    pub average_ranks: Vec<f64>,
+    pub test: TestResult,
+    /// The critical difference of average ranks for the Nemenyi test
+    pub critical_difference: f64,
+    /// Nemenyi post-hoc comparisons of all pairs of solvers
+    pub post_hoc: Vec<PairwiseComparison>,
+}
+
+/// Complementary error function, fractional error below 1.2e-7
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let r = t * (-z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807
+                            + t * (-1.13520398
+                                + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277)))))))))
+        .exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+/// Cumulative distribution function of the standard normal distribution
+pub fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Density of the standard normal distribution
+fn normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * PI).sqrt()
+}
+
+/// Natural logarithm of the gamma function (Lanczos approximation)
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+    let mut y = x;
+    let tmp = x + 5.5 - (x + 0.5) * (x + 5.5).ln();
+    let mut series = 1.000000000190015;
+    for coefficient in COEFFICIENTS {
+        y += 1.0;
+        series += coefficient / y;
+    }
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
+
+/// Regularized upper incomplete gamma function Q(a, x)
+fn gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let ln_prefactor = -x + a * x.ln() - ln_gamma(a);
+    if x < a + 1.0 {
+        // Series representation of P(a, x)
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        let mut ap = a;
+        for _ in 0..1000 {
+            ap += 1.0;
+            term *= x / ap;
+            sum += term;
+            if term.abs() < sum.abs() * 1e-15 {
+                break;
+            }
+        }
+        1.0 - sum * ln_prefactor.exp()
+    } else {
+        // Continued fraction representation of Q(a, x)
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-15 {
+                break;
+            }
+        }
+        ln_prefactor.exp() * h
+    }
+}
+
+/// Survival function (1 - cdf) of the chi-square distribution
+pub fn chi_square_sf(x: f64, degrees_of_freedom: f64) -> f64 {
+    gamma_q(degrees_of_freedom / 2.0, x / 2.0)
+}
+
+/// Cumulative distribution function of the studentized range of `k`
+/// normal variables with infinite degrees of freedom
+pub fn studentized_range_cdf(q: f64, k: usize) -> f64 {
+    if q <= 0.0 {
+        return 0.0;
+    }
+    // Simpson's rule over the density of the largest variable
+    let (low, high, steps) = (-8.0, 8.0 + q, 4000);
+    let h = (high - low) / steps as f64;
+    let integrand = |z: f64| normal_pdf(z) * (normal_cdf(z) - normal_cdf(z - q)).powi(k as i32 - 1);
+    let mut sum = integrand(low) + integrand(high);
+    for i in 1..steps {
+        let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+        sum += weight * integrand(low + i as f64 * h);
+    }
+    (k as f64 * sum * h / 3.0).min(1.0)
+}
+
+/// Returns the upper `alpha` quantile of the studentized range of `k`
+/// normal variables with infinite degrees of freedom
+fn studentized_range_quantile(alpha: f64, k: usize) -> f64 {
+    let (mut low, mut high) = (0.0, 20.0);
+    for _ in 0..60 {
+        let middle = (low + high) / 2.0;
+        if 1.0 - studentized_range_cdf(middle, k) > alpha {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+/// Returns the ranks (starting from 1) of the values in ascending order,
+/// tied values get the average of their ranks
+pub fn rank(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap());
+    let mut ranks = vec![0.0; values.len()];
+    let mut i = 0;
+    while i < order.len() {
+        let mut j = i;
+        while j + 1 < order.len() && values[order[j + 1]] == values[order[i]] {
+            j += 1;
+        }
+        let average_rank = (i + j) as f64 / 2.0 + 1.0;
+        for &index in &order[i..=j] {
+            ranks[index] = average_rank;
+        }
+        i = j + 1;
+    }
+    ranks
+}
+
+/// Returns the sum of t^3 - t over groups of t tied values
+fn tie_correction(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mut correction = 0.0;
+    let mut i = 0;
+    while i < sorted.len() {
+        let mut j = i;
+        while j + 1 < sorted.len() && sorted[j + 1] == sorted[i] {
+            j += 1;
+        }
+        let t = (j - i + 1) as f64;
+        correction += t * t * t - t;
+        i = j + 1;
+    }
+    correction
+}
+
+/// Two-sided p-value of a statistic from its exact null distribution,
+/// given as counts of every integer value of the statistic
+fn exact_two_sided_p_value(counts: &[f64], statistic: f64) -> f64 {
+    let total: f64 = counts.iter().sum();
+    let statistic = statistic.round() as usize;
+    let lower: f64 = counts[..=statistic].iter().sum();
+    let upper: f64 = counts[statistic..].iter().sum();
+    (2.0 * lower.min(upper) / total).min(1.0)
+}
+
+/// Two-sided p-value of a normally approximated statistic
+/// with continuity correction
+fn normal_two_sided_p_value(statistic: f64, mean: f64, variance: f64) -> f64 {
+    if variance <= 0.0 {
+        return 1.0;
+    }
+    let z = ((statistic - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+    (2.0 * (1.0 - normal_cdf(z))).min(1.0)
+}
+
+/// Wilcoxon signed-rank test of paired samples. The statistic is the sum of
+/// ranks of positive differences. Zero differences are discarded. The exact
+/// distribution is used for up to 25 pairs without ties, otherwise
+/// the normal approximation with tie correction.
+pub fn wilcoxon_signed_rank(x: &[f64], y: &[f64]) -> TestResult {
+    assert_eq!(x.len(), y.len(), "Wilcoxon signed-rank test requires paired samples");
+    let differences: Vec<f64> = x
+        .iter()
+        .zip(y)
+        .map(|(a, b)| a - b)
+        .filter(|d| *d != 0.0)
+        .collect();
+    let n = differences.len();
+    if n == 0 {
+        return TestResult { statistic: 0.0, p_value: 1.0 };
+    }
+    let absolute: Vec<f64> = differences.iter().map(|d| d.abs()).collect();
+    let ranks = rank(&absolute);
+    let statistic: f64 = ranks
+        .iter()
+        .zip(&differences)
+        .filter(|(_, d)| **d > 0.0)
+        .map(|(r, _)| r)
+        .sum();
+    let ties = tie_correction(&absolute);
+
+    let p_value = if ties == 0.0 && n <= 25 {
+        // Distribution of the sum of a random subset of ranks 1..n
+        let max_sum = n * (n + 1) / 2;
+        let mut counts = vec![0.0; max_sum + 1];
+        counts[0] = 1.0;
+        for r in 1..=n {
+            for s in (r..=max_sum).rev() {
+                counts[s] += counts[s - r];
+            }
+        }
+        exact_two_sided_p_value(&counts, statistic)
+    } else {
+        let n = n as f64;
+        let mean = n * (n + 1.0) / 4.0;
+        let variance = n * (n + 1.0) * (2.0 * n + 1.0) / 24.0 - ties / 48.0;
+        normal_two_sided_p_value(statistic, mean, variance)
+    };
+    TestResult { statistic, p_value }
+}
+
+/// Mann-Whitney U test of independent samples. The statistic is U of
+/// the first sample. The exact distribution is used for samples of up to
+/// 30 values without ties, otherwise the normal approximation
+/// with tie correction.
+pub fn mann_whitney_u(x: &[f64], y: &[f64]) -> TestResult {
+    let (m, n) = (x.len(), y.len());
+    if m == 0 || n == 0 {
+        return TestResult { statistic: 0.0, p_value: 1.0 };
+    }
+    let pooled: Vec<f64> = x.iter().chain(y).copied().collect();
+    let ranks = rank(&pooled);
+    let rank_sum: f64 = ranks[..m].iter().sum();
+    let statistic = rank_sum - (m * (m + 1)) as f64 / 2.0;
+    let ties = tie_correction(&pooled);
+
+    let p_value = if ties == 0.0 && m <= 30 && n <= 30 {
+        // U is the sum of positions of the first sample in the pooled order
+        // minus m(m-1)/2, so count m-subsets of 0..m+n by their sum
+        let max_u = m * n;
+        let mut counts = vec![vec![0.0; max_u + 1]; m + 1];
+        counts[0][0] = 1.0;
+        for position in 0..m + n {
+            for chosen in (1..=m.min(position + 1)).rev() {
+                // Shift by the number of second sample values before the position
+                let shift = position + 1 - chosen;
+                if shift > n {
+                    continue;
+                }
+                for u in (shift..=max_u).rev() {
+                    counts[chosen][u] += counts[chosen - 1][u - shift];
+                }
+            }
+        }
+        exact_two_sided_p_value(&counts[m], statistic)
+    } else {
+        let (m, n) = (m as f64, n as f64);
+        let total = m + n;
+        let mean = m * n / 2.0;
+        let variance = m * n / 12.0 * ((total + 1.0) - ties / (total * (total - 1.0)));
+        normal_two_sided_p_value(statistic, mean, variance)
+    };
+    TestResult { statistic, p_value }
+}
+
+/// Returns Holm-adjusted p-values (in the original order)
+pub fn holm_correction(p_values: &[f64]) -> Vec<f64> {
+    let m = p_values.len();
+    let mut order: Vec<usize> = (0..m).collect();
+    order.sort_by(|&a, &b| p_values[a].partial_cmp(&p_values[b]).unwrap());
+    let mut adjusted = vec![0.0; m];
+    let mut running_max: f64 = 0.0;
+    for (i, &index) in order.iter().enumerate() {
+        running_max = running_max.max(((m - i) as f64 * p_values[index]).min(1.0));
+        adjusted[index] = running_max;
+    }
+    adjusted
+}
+
+/// Friedman test of a matrix with a row per block (instance) and a column
+/// per treatment (solver). Lower values get lower ranks.
+/// Returns the test result and the average rank of every column.
+pub fn friedman_test(matrix: &[Vec<f64>]) -> (TestResult, Vec<f64>) {
+    let n = matrix.len() as f64;
+    let k = matrix.first().map_or(0, |row| row.len());
+    let mut average_ranks = vec![0.0; k];
+    let mut ties = 0.0;
+    for row in matrix {
+        for (j, r) in rank(row).iter().enumerate() {
+            average_ranks[j] += r / n;
+        }
+        ties += tie_correction(row);
+    }
+    if k < 2 || matrix.is_empty() {
+        return (TestResult { statistic: 0.0, p_value: 1.0 }, average_ranks);
+    }
+    let kf = k as f64;
+    let sum_squares: f64 = average_ranks.iter().map(|r| r * r).sum();
+    let mut statistic = 12.0 * n / (kf * (kf + 1.0)) * sum_squares - 3.0 * n * (kf + 1.0);
+    let denominator = 1.0 - ties / (n * (kf * kf * kf - kf));
+    if denominator > 0.0 {
+        statistic /= denominator;
+    }
+    let p_value = chi_square_sf(statistic, kf - 1.0);
+    (TestResult { statistic, p_value }, average_ranks)
+}
+
+/// Critical difference of average ranks of `k` treatments over `n_blocks`
+/// blocks for the Nemenyi test at significance level `alpha`
+pub fn nemenyi_critical_difference(k: usize, n_blocks: usize, alpha: f64) -> f64 {
+    let q_alpha = studentized_range_quantile(alpha, k) / std::f64::consts::SQRT_2;
+    q_alpha * ((k * (k + 1)) as f64 / (6.0 * n_blocks as f64)).sqrt()
+}
+
+/// Returns the sorted solver names and instances common to all solvers
+fn solvers_and_common_instances(result_sets: &[ResultSet]) -> (Vec<String>, Vec<String>) {
+    let solvers: BTreeSet<&String> = result_sets.iter().map(|r| &r.solver).collect();
+    let instances: BTreeSet<&String> = result_sets.iter().map(|r| &r.instance).collect();
+    let common = instances
+        .into_iter()
+        .filter(|&instance| {
+            solvers.iter().all(|&solver| {
+                result_sets.iter().any(|r| &r.instance == instance && &r.solver == solver)
+            })
+        })
+        .cloned()
+        .collect();
+    (solvers.into_iter().cloned().collect(), common)
+}
+
+/// Returns the result set of a solver on an instance
+fn find_result_set<'a>(result_sets: &'a [ResultSet], instance: &str, solver: &str) -> &'a ResultSet {
+    result_sets
+        .iter()
+        .find(|r| r.instance == instance && r.solver == solver)
+        .unwrap()
+}
+
+/// Returns the mean values with a row per instance and a column per solver
+fn mean_matrix(result_sets: &[ResultSet], solvers: &[String], instances: &[String]) -> Vec<Vec<f64>> {
+    instances
+        .iter()
+        .map(|instance| {
+            solvers
+                .iter()
+                .map(|solver| find_result_set(result_sets, instance, solver).mean())
+                .collect()
+        })
+        .collect()
+}
+
+/// Compares every pair of solvers with the Mann-Whitney U test on the runs
+/// of each instance, and with the Wilcoxon signed-rank test over the
+/// instances solved by all solvers. Like in the Friedman test, the Wilcoxon
+/// test compares the ranks of the mean values within each instance, so
+/// instances of large costs do not dominate. P-values are Holm-corrected
+/// within each instance and within the Wilcoxon tests.
+pub fn pairwise_tests(result_sets: &[ResultSet], alpha: f64) -> Vec<PairwiseComparison> {
+    let (solvers, instances) = solvers_and_common_instances(result_sets);
+    let mut comparisons: Vec<PairwiseComparison> = vec![];
+
+    let mut add_family = |mut family: Vec<PairwiseComparison>| {
+        let p_values: Vec<f64> = family.iter().map(|c| c.p_value).collect();
+        for (comparison, adjusted) in family.iter_mut().zip(holm_correction(&p_values)) {
+            comparison.adjusted_p_value = adjusted;
+            comparison.significant = adjusted < alpha;
+        }
+        comparisons.append(&mut family);
+    };
+    let comparison = |instance: Option<&String>, a: &String, b: &String, test, result: TestResult| {
+        PairwiseComparison {
+            instance: instance.cloned(),
+            solver_a: a.clone(),
+            solver_b: b.clone(),
+            test,
+            statistic: result.statistic,
+            p_value: result.p_value,
+            adjusted_p_value: result.p_value,
+            significant: false,
+        }
+    };
+
+    for instance in &instances {
+        let mut family = vec![];
+        for (i, a) in solvers.iter().enumerate() {
+            for b in &solvers[i + 1..] {
+                let x = &find_result_set(result_sets, instance, a).values;
+                let y = &find_result_set(result_sets, instance, b).values;
+                family.push(comparison(Some(instance), a, b, "mann-whitney-u", mann_whitney_u(x, y)));
+            }
+        }
+        add_family(family);
+    }
+
+    if instances.len() > 1 {
+        let ranks: Vec<Vec<f64>> = mean_matrix(result_sets, &solvers, &instances).iter().map(|row| rank(row)).collect();
+        let mut family = vec![];
+        for (i, a) in solvers.iter().enumerate() {
+            for (j, b) in solvers.iter().enumerate().skip(i + 1) {
+                let x: Vec<f64> = ranks.iter().map(|row| row[i]).collect();
+                let y: Vec<f64> = ranks.iter().map(|row| row[j]).collect();
+                family.push(comparison(None, a, b, "wilcoxon-signed-rank", wilcoxon_signed_rank(&x, &y)));
+            }
+        }
+        add_family(family);
+    }
+    comparisons
+}
+
+/// Runs the Friedman test on the mean values of the solvers over the
+/// instances solved by all solvers, followed by the Nemenyi post-hoc test
+pub fn friedman_nemenyi(result_sets: &[ResultSet], alpha: f64) -> FriedmanResult {
+    let (solvers, instances) = solvers_and_common_instances(result_sets);
+    let matrix = mean_matrix(result_sets, &solvers, &instances);
+    let (test, average_ranks) = friedman_test(&matrix);
+    let k = solvers.len();
+    let n_blocks = instances.len().max(1);
+    let critical_difference = nemenyi_critical_difference(k, n_blocks, alpha);
+
+    let standard_error = ((k * (k + 1)) as f64 / (6.0 * n_blocks as f64)).sqrt();
+    let mut post_hoc = vec![];
+    for i in 0..k {
+        for j in i + 1..k {
+            let difference = (average_ranks[i] - average_ranks[j]).abs();
+            let q = difference / standard_error * std::f64::consts::SQRT_2;
+            let p_value = 1.0 - studentized_range_cdf(q, k);
+            post_hoc.push(PairwiseComparison {
+                instance: None,
+                solver_a: solvers[i].clone(),
+                solver_b: solvers[j].clone(),
+                test: "nemenyi",
+                statistic: difference,
+                p_value,
+                adjusted_p_value: p_value,
+                significant: difference > critical_difference,
+            });
+        }
+    }
+    FriedmanResult {
+        solvers,
+        instances,
+        average_ranks,
+        test,
+        critical_difference,
+        post_hoc,
+    }
+}
+
+/// Saves pairwise comparisons in a csv file according to the provided path
+pub fn save_pairwise_comparisons(
+    comparisons: &[PairwiseComparison],
+    path: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut wtr = Writer::from_path(path)?;
+    // Write column names
+    wtr.write_record([
+        "instance", "solver_a", "solver_b", "test", "statistic",
+        "p_value", "adjusted_p_value", "significant",
+    ])?;
+    for comparison in comparisons {
+        wtr.write_record(&[
+            comparison.instance.clone().unwrap_or_else(|| "all".to_string()),
+            comparison.solver_a.clone(),
+            comparison.solver_b.clone(),
+            comparison.test.to_string(),
+            comparison.statistic.to_string(),
+            comparison.p_value.to_string(),
+            comparison.adjusted_p_value.to_string(),
+            comparison.significant.to_string(),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Saves the critical-difference ranking of the Friedman test in a csv file:
+/// solvers ordered by average rank, each with the solvers it is not
+/// significantly different from
+pub fn save_friedman_ranking(result: &FriedmanResult, path: &str) -> Result<(), Box<dyn Error>> {
+    let mut wtr = Writer::from_path(path)?;
+    // Write column names
+    wtr.write_record([
+        "position", "solver", "average_rank", "friedman_statistic", "friedman_p_value",
+        "critical_difference", "not_different_from",
+    ])?;
+    let mut order: Vec<usize> = (0..result.solvers.len()).collect();
+    order.sort_by(|&a, &b| result.average_ranks[a].partial_cmp(&result.average_ranks[b]).unwrap());
+    for (position, &i) in order.iter().enumerate() {
+        let not_different: Vec<&str> = order
+            .iter()
+            .filter(|&&j| {
+                j != i && (result.average_ranks[i] - result.average_ranks[j]).abs() <= result.critical_difference
+            })
+            .map(|&j| result.solvers[j].as_str())
+            .collect();
+        wtr.write_record(&[
+            (position + 1).to_string(),
+            result.solvers[i].clone(),
+            result.average_ranks[i].to_string(),
+            result.test.statistic.to_string(),
+            result.test.p_value.to_string(),
+            result.critical_difference.to_string(),
+            not_different.join(";"),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!((actual - expected).abs() < tolerance, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn special_functions() {
+        assert_close(erfc(0.5), 0.4795001221869535, 1e-7);
+        assert_close(erfc(-1.0), 1.8427007929497148, 1e-7);
+        assert_close(normal_cdf(1.959963984540054), 0.975, 1e-7);
+        assert_close(ln_gamma(5.0), 24f64.ln(), 1e-10);
+        assert_close(ln_gamma(0.5), PI.sqrt().ln(), 1e-10);
+        // Upper 5% critical values of the chi-square distribution
+        assert_close(chi_square_sf(3.841458820694124, 1.0), 0.05, 1e-6);
+        assert_close(chi_square_sf(11.070497693516351, 5.0), 0.05, 1e-6);
+    }
+
+    #[test]
+    fn wilcoxon_exact() {
+        // Differences of Darwin's Zea mays data: W- = 24, W+ = 96, p = 0.0413
+        let d = [6., 8., 14., 16., 23., 24., 28., 29., 41., -48., 49., 56., 60., -67., 75.];
+        let result = wilcoxon_signed_rank(&d, &[0.0; 15]);
+        assert_eq!(result.statistic, 96.0);
+        assert_close(result.p_value, 0.041259765625, 1e-12);
+        // All five differences positive: p = 2 / 2^5
+        assert_close(wilcoxon_signed_rank(&[2., 3., 4., 5., 6.], &[1.; 5]).p_value, 0.0625, 1e-12);
+    }
+
+    #[test]
+    fn mann_whitney() {
+        let (x, y) = ([19., 22., 16., 29., 24.], [20., 11., 17., 12.]);
+        let result = mann_whitney_u(&x, &y);
+        assert_eq!(result.statistic, 17.0);
+        assert_close(result.p_value, 1.0 / 9.0, 1e-12);
+        // Normal approximation with continuity correction of the same sample
+        assert_close(normal_two_sided_p_value(17.0, 10.0, 50.0 / 3.0), 0.11134688653314041, 1e-6);
+        // Ties switch to the normal approximation: U = 1.5, variance 5.1, p = 0.2683
+        let result = mann_whitney_u(&[1., 2., 3.], &[2., 4., 5.]);
+        assert_eq!(result.statistic, 1.5);
+        assert_close(result.p_value, 0.26828588367711736, 1e-6);
+    }
+
+    #[test]
+    fn friedman() {
+        // Identical rankings of 3 treatments over 4 blocks: chi2 = 8, p = exp(-4)
+        let matrix = vec![vec![1., 2., 3.], vec![10., 20., 30.], vec![5., 6., 7.], vec![0., 1., 9.]];
+        let (result, average_ranks) = friedman_test(&matrix);
+        assert_eq!(average_ranks, vec![1.0, 2.0, 3.0]);
+        assert_close(result.statistic, 8.0, 1e-12);
+        assert_close(result.p_value, (-4f64).exp(), 1e-9);
+    }
+
+    #[test]
+    fn nemenyi() {
+        // Critical values q_0.05 of Demsar (2006) for 2 to 5 classifiers
+        for (k, q) in [(2, 1.960), (3, 2.343), (4, 2.569), (5, 2.728)] {
+            assert_close(studentized_range_quantile(0.05, k) / std::f64::consts::SQRT_2, q, 1e-3);
+        }
+        // Demsar's example: 4 classifiers on 14 data sets, CD = 1.25
+        assert_close(nemenyi_critical_difference(4, 14, 0.05), 1.25, 5e-3);
+    }
+}
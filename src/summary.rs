@@ -0,0 +1,280 @@
+use crate::experiment::ExperimentResults;
+
+use csv::Writer;
+use meansd::MeanSD;
+use std::error::Error;
+
+/// Returns the q-quantile (0 <= q <= 1) of sorted values,
+/// linearly interpolating between the closest ranks
+pub fn quantile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Returns the relative gap (in %) of an evaluation to the optimum.
+/// An optimum of 0 is treated as 1 to keep the gap finite.
+pub fn relative_gap(evaluation: i32, optimum: i32) -> f64 {
+    100.0 * (evaluation as f64 - optimum as f64) / optimum.abs().max(1) as f64
+}
+
+/// Returns the largest evaluation within `gap` % of the optimum
+pub fn target_evaluation(optimum: i32, gap: f64) -> i32 {
+    optimum + (gap / 100.0 * optimum.abs().max(1) as f64).floor() as i32
+}
+
+/// Returns the mean and sample standard deviation of the values
+fn mean_std<I: Iterator<Item = f64>>(values: I) -> (f64, f64) {
+    let mut meansd = MeanSD::default();
+    for value in values {
+        meansd.update(value);
+    }
+    (meansd.mean(), meansd.sstdev())
+}
+
+/// Summary statistics of all runs of one solver on one instance
+#[derive(Debug, Clone)]
+pub struct RunSummary {
+    pub instance: String,
+    pub solver: String,
+    pub n_runs: usize,
+    /// Optimal (or best known) evaluation, if available
+    pub optimum: Option<i32>,
+    pub min: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub std: f64,
+    /// Relative gap (%) of the best run
+    pub best_gap: Option<f64>,
+    /// Mean relative gap (%) over runs
+    pub avg_gap: Option<f64>,
+    /// The gap (%) at which a run counts as successful
+    pub target_gap: f64,
+    /// Fraction of runs that reached the target gap
+    pub success_rate: Option<f64>,
+    /// Mean time (ms) to reach the target gap over the successful runs
+    pub mean_time_to_target: Option<f64>,
+    pub mean_time: f64,
+    pub mean_iterations: f64,
+    pub mean_updates: f64,
+    /// The number of distinct final solutions
+    pub n_distinct: usize,
+    /// Mean Hamming distance between the final solutions
+    pub mean_distance: f64,
+    /// Normalized entropy of the final assignments (0 if all agree)
+    pub assignment_entropy: f64,
+}
+
+impl RunSummary {
+    /// Computes the summary of experiment results. Gap based statistics
+    /// are only available if the optimum is known.
+    pub fn new(
+        instance: &str,
+        solver: &str,
+        results: &ExperimentResults,
+        optimum: Option<i32>,
+        target_gap: f64,
+    ) -> RunSummary {
+        let evaluations = results.final_evaluations();
+        let mut sorted: Vec<f64> = evaluations.iter().map(|&e| e as f64).collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let (mean, std) = mean_std(sorted.iter().copied());
+        let diversity = results.diversity();
+
+        let mut best_gap = None;
+        let mut avg_gap = None;
+        let mut success_rate = None;
+        let mut mean_time_to_target = None;
+        if let Some(optimum) = optimum {
+            if !evaluations.is_empty() {
+                let gaps: Vec<f64> = evaluations.iter().map(|&e| relative_gap(e, optimum)).collect();
+                best_gap = gaps.iter().copied().reduce(f64::min);
+                avg_gap = Some(gaps.iter().sum::<f64>() / gaps.len() as f64);
+
+                let times_to_target: Vec<u128> = results
+                    .time_to_target(target_evaluation(optimum, target_gap))
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                success_rate = Some(times_to_target.len() as f64 / evaluations.len() as f64);
+                if !times_to_target.is_empty() {
+                    let total: u128 = times_to_target.iter().sum();
+                    mean_time_to_target = Some(total as f64 / times_to_target.len() as f64);
+                }
+            }
+        }
+
+        RunSummary {
+            instance: instance.to_string(),
+            solver: solver.to_string(),
+            n_runs: evaluations.len(),
+            optimum,
+            min: quantile(&sorted, 0.0),
+            q1: quantile(&sorted, 0.25),
+            median: quantile(&sorted, 0.5),
+            q3: quantile(&sorted, 0.75),
+            max: quantile(&sorted, 1.0),
+            mean,
+            std,
+            best_gap,
+            avg_gap,
+            target_gap,
+            success_rate,
+            mean_time_to_target,
+            mean_time: mean_std(results.elapsed_time.iter().map(|&t| t as f64)).0,
+            mean_iterations: mean_std(results.iterations.iter().map(|&i| i as f64)).0,
+            mean_updates: mean_std(results.updates.iter().map(|&u| u as f64)).0,
+            n_distinct: diversity.n_distinct,
+            mean_distance: diversity.mean_hamming,
+            assignment_entropy: diversity.assignment_entropy,
+        }
+    }
+}
+
+/// Formats an optional value, empty if missing
+fn format_option(value: Option<f64>, precision: usize) -> String {
+    match value {
+        Some(value) => format!("{:.*}", precision, value),
+        None => String::new(),
+    }
+}
+
+/// Saves the summaries in a tidy csv file (one row per instance and solver)
+pub fn save_summaries(summaries: &[RunSummary], path: &str) -> Result<(), Box<dyn Error>> {
+    let mut wtr = Writer::from_path(path)?;
+    // Write column names
+    wtr.write_record([
+        "instance", "solver", "n_runs", "optimum", "min", "q1", "median", "q3", "max",
+        "mean", "std", "best_gap", "avg_gap", "target_gap", "success_rate",
+        "mean_time_to_target", "mean_time", "mean_iterations", "mean_updates",
+        "n_distinct", "mean_distance", "assignment_entropy",
+    ])?;
+    for summary in summaries {
+        wtr.write_record(&[
+            summary.instance.clone(),
+            summary.solver.clone(),
+            summary.n_runs.to_string(),
+            summary.optimum.map(|o| o.to_string()).unwrap_or_default(),
+            summary.min.to_string(),
+            summary.q1.to_string(),
+            summary.median.to_string(),
+            summary.q3.to_string(),
+            summary.max.to_string(),
+            summary.mean.to_string(),
+            summary.std.to_string(),
+            format_option(summary.best_gap, 6),
+            format_option(summary.avg_gap, 6),
+            summary.target_gap.to_string(),
+            format_option(summary.success_rate, 6),
+            format_option(summary.mean_time_to_target, 3),
+            summary.mean_time.to_string(),
+            summary.mean_iterations.to_string(),
+            summary.mean_updates.to_string(),
+            summary.n_distinct.to_string(),
+            summary.mean_distance.to_string(),
+            summary.assignment_entropy.to_string(),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Column names of the report tables
+const TABLE_HEADER: [&str; 10] = [
+    "Instance", "Solver", "Best", "Median", "Mean", "Std",
+    "Best gap [%]", "Avg gap [%]", "Success", "Time [ms]",
+];
+
+/// Formats the summaries as rows of the report tables
+fn table_rows(summaries: &[RunSummary]) -> Vec<Vec<String>> {
+    summaries
+        .iter()
+        .map(|summary| {
+            vec![
+                summary.instance.clone(),
+                summary.solver.clone(),
+                format!("{:.0}", summary.min),
+                format!("{:.0}", summary.median),
+                format!("{:.1}", summary.mean),
+                format!("{:.1}", summary.std),
+                format_option(summary.best_gap, 2),
+                format_option(summary.avg_gap, 2),
+                format_option(summary.success_rate, 2),
+                format!("{:.1}", summary.mean_time),
+            ]
+        })
+        .collect()
+}
+
+/// Returns the summaries as a Markdown table
+pub fn summaries_to_markdown(summaries: &[RunSummary]) -> String {
+    let mut table = format!("| {} |\n", TABLE_HEADER.join(" | "));
+    table += &format!("|{}\n", "---|".repeat(TABLE_HEADER.len()));
+    for row in table_rows(summaries) {
+        table += &format!("| {} |\n", row.join(" | "));
+    }
+    table
+}
+
+/// Escapes characters with a special meaning in LaTeX
+fn escape_latex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '_' | '%' | '&' | '#' | '$' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns the summaries as a LaTeX tabular environment
+pub fn summaries_to_latex(summaries: &[RunSummary]) -> String {
+    let header: Vec<String> = TABLE_HEADER.iter().map(|h| escape_latex(h)).collect();
+    let mut table = format!("\\begin{{tabular}}{{ll{}}}\n", "r".repeat(TABLE_HEADER.len() - 2));
+    table += "\\hline\n";
+    table += &format!("{} \\\\\n", header.join(" & "));
+    table += "\\hline\n";
+    for row in table_rows(summaries) {
+        let row: Vec<String> = row.iter().map(|cell| escape_latex(cell)).collect();
+        table += &format!("{} \\\\\n", row.join(" & "));
+    }
+    table += "\\hline\n\\end{tabular}\n";
+    table
+}
+
+/// Escapes characters with a special meaning in HTML
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Returns the summaries as an HTML table
+pub fn summaries_to_html(summaries: &[RunSummary]) -> String {
+    let mut table = String::from("<table>\n<tr>");
+    for header in TABLE_HEADER {
+        table += &format!("<th>{}</th>", escape_html(header));
+    }
+    table += "</tr>\n";
+    for row in table_rows(summaries) {
+        table += "<tr>";
+        for cell in row {
+            table += &format!("<td>{}</td>", escape_html(&cell));
+        }
+        table += "</tr>\n";
+    }
+    table += "</table>\n";
+    table
+}
@@ -0,0 +1,80 @@
+use std::time::Instant;
+
+/// A point of a best-so-far trace: the moment a solver first
+/// visited a solution better than all solutions visited before
+#[derive(Debug, Clone, Copy)]
+pub struct TracePoint {
+    /// Time (ms) since the start of the run
+    pub time: f64,
+    /// The number of solutions (or moves) evaluated since the start of the run
+    pub evaluations: u64,
+    /// Evaluation of the new best solution
+    pub evaluation: i32,
+}
+
+/// Records the best-so-far trace of a run. Solvers report the number of
+/// evaluated solutions and the evaluation of the solutions they move to,
+/// only improvements of the best one are stored.
+#[derive(Debug, Clone)]
+pub struct Trace {
+    started: Instant,
+    /// Time (ms) elapsed in the run before the trace was started
+    time_offset: f64,
+    evaluations: u64,
+    best: i32,
+    points: Vec<TracePoint>,
+}
+
+impl Default for Trace {
+    fn default() -> Self {
+        Trace::new()
+    }
+}
+
+impl Trace {
+    /// Constructor, the clock starts now
+    pub fn new() -> Trace {
+        Trace::starting_at(0)
+    }
+
+    /// Constructor of a trace of a resumed run which had already
+    /// been running for `elapsed` ms
+    pub fn starting_at(elapsed: u128) -> Trace {
+        Trace {
+            started: Instant::now(),
+            time_offset: elapsed as f64,
+            evaluations: 0,
+            best: i32::MAX,
+            points: vec![],
+        }
+    }
+
+    /// Counts `evaluations` more evaluated solutions and records
+    /// the evaluation of the current solution if it is a new best
+    pub fn update(&mut self, evaluations: u64, evaluation: i32) {
+        self.evaluations += evaluations;
+        if evaluation < self.best {
+            self.best = evaluation;
+            self.points.push(TracePoint {
+                time: self.time_offset + self.started.elapsed().as_secs_f64() * 1000.0,
+                evaluations: self.evaluations,
+                evaluation,
+            });
+        }
+    }
+
+    /// Returns the number of evaluated solutions
+    pub fn get_evaluations(&self) -> u64 {
+        self.evaluations
+    }
+
+    /// Returns the recorded points, in order of time
+    pub fn get_points(&self) -> &Vec<TracePoint> {
+        &self.points
+    }
+
+    /// Consumes the trace and returns the recorded points
+    pub fn into_points(self) -> Vec<TracePoint> {
+        self.points
+    }
+}
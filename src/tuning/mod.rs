@@ -0,0 +1,3 @@
+pub mod parameter_search;
+pub mod parameters;
+pub mod racing;
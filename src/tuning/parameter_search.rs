@@ -0,0 +1,144 @@
+use csv::Writer;
+use std::error::Error;
+use std::path::Path;
+
+use crate::experiment::Experiment;
+use crate::qap_problem::QapProblem;
+use crate::solution::Solution;
+use crate::solvers::registry::{build_solver, Parameters};
+use crate::summary::RunSummary;
+use crate::tuning::parameters::{resolve_parameters, ParameterDomain};
+
+/// Exhaustive (grid) or random search over solver parameters.
+/// Every configuration is evaluated with `n_runs` runs on every instance.
+pub struct ParameterSearch {
+    solver: String,
+    domains: Vec<ParameterDomain>,
+    configurations: Vec<Parameters>,
+    n_runs: usize,
+    /// The gap (%) at which a run counts as successful
+    target_gap: f64,
+}
+
+impl ParameterSearch {
+    /// Constructor of a grid search over all combinations of the values
+    /// of the parameters. Values of parameters expressed as fractions
+    /// of n are resolved for every instance.
+    pub fn grid(solver: &str, ranges: Vec<(ParameterDomain, Vec<f64>)>, n_runs: usize) -> ParameterSearch {
+        let mut configurations = vec![Parameters::new()];
+        for (domain, values) in &ranges {
+            let mut extended = Vec::with_capacity(configurations.len() * values.len());
+            for configuration in &configurations {
+                for value in values {
+                    let mut configuration = configuration.clone();
+                    configuration.insert(domain.name.clone(), *value);
+                    extended.push(configuration);
+                }
+            }
+            configurations = extended;
+        }
+        ParameterSearch {
+            solver: solver.to_string(),
+            domains: ranges.into_iter().map(|(domain, _)| domain).collect(),
+            configurations,
+            n_runs,
+            target_gap: 1.0,
+        }
+    }
+
+    /// Constructor of a random search over `n_samples` configurations
+    /// drawn uniformly from the domains
+    pub fn random(solver: &str, domains: Vec<ParameterDomain>, n_samples: usize, n_runs: usize) -> ParameterSearch {
+        let mut rng = rand::thread_rng();
+        let configurations = (0..n_samples)
+            .map(|_| {
+                domains
+                    .iter()
+                    .map(|domain| (domain.name.clone(), domain.sample(&mut rng)))
+                    .collect()
+            })
+            .collect();
+        ParameterSearch {
+            solver: solver.to_string(),
+            domains,
+            configurations,
+            n_runs,
+            target_gap: 1.0,
+        }
+    }
+
+    /// Sets the gap (%) at which a run counts as successful
+    pub fn set_target_gap(&mut self, target_gap: f64) {
+        self.target_gap = target_gap
+    }
+
+    /// Returns the searched configurations (before resolving fractions of n)
+    pub fn get_configurations(&self) -> &Vec<Parameters> {
+        &self.configurations
+    }
+
+    /// Evaluates every configuration on every instance and writes one csv
+    /// row per configuration per instance. The optimum is read from the
+    /// .sln file next to the instance, gap statistics are empty without it.
+    pub fn run(&self, instance_paths: &[String], path: &str) -> Result<(), Box<dyn Error>> {
+        let mut wtr = Writer::from_path(path)?;
+        // Write column names
+        let mut header = vec!["instance".to_string(), "configuration".to_string()];
+        header.extend(self.domains.iter().map(|d| d.name.clone()));
+        header.extend(
+            [
+                "eval_mean", "eval_std", "eval_min", "best_gap", "avg_gap",
+                "success_rate", "time_mean", "time_std",
+            ]
+            .iter()
+            .map(|s| s.to_string()),
+        );
+        wtr.write_record(&header)?;
+
+        for instance_path in instance_paths {
+            let instance = Path::new(instance_path)
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+            println!("{}", instance);
+            let mut problem = QapProblem::new(instance_path)?;
+            let sln_path = Path::new(instance_path).with_extension("sln");
+            let optimum = Solution::read_sln(&sln_path.to_string_lossy()).ok().map(|s| s.get_eval());
+
+            for (i, configuration) in self.configurations.iter().enumerate() {
+                let parameters = resolve_parameters(&self.domains, configuration, problem.get_n());
+                println!("{} configuration {}: {:?}", self.solver, i, parameters);
+                let mut solver = build_solver(&self.solver, &mut problem, &parameters)?;
+                let mut experiment = Experiment::new(&mut *solver, self.n_runs);
+                experiment.run();
+                // Collect the experiment statistics
+                let (time_mean, time_std) = experiment.get_elapsed_time_mean_std();
+                let summary = RunSummary::new(
+                    &instance,
+                    &self.solver,
+                    experiment.get_results(),
+                    optimum,
+                    self.target_gap,
+                );
+                let format_option = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+                let mut record = vec![instance.clone(), i.to_string()];
+                record.extend(self.domains.iter().map(|d| parameters[&d.name].to_string()));
+                record.extend([
+                    summary.mean.to_string(),
+                    summary.std.to_string(),
+                    summary.min.to_string(),
+                    format_option(summary.best_gap),
+                    format_option(summary.avg_gap),
+                    format_option(summary.success_rate),
+                    time_mean.to_string(),
+                    time_std.to_string(),
+                ]);
+                wtr.write_record(&record)?;
+            }
+            // Keep the results of finished instances on disk
+            wtr.flush()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use rand::Rng;
+use serde_json::{Map, Number, Value};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+
+use crate::catalogue::{instance_family, instance_variant};
+use crate::solvers::registry::Parameters;
+
+/// The range of values a solver parameter can take
+#[derive(Debug, Clone)]
+pub struct ParameterDomain {
+    pub name: String,
+    pub min: f64,
+    pub max: f64,
+    /// Whether the resolved value is rounded to an integer
+    pub integer: bool,
+    /// Whether the value is a fraction of the instance size n
+    pub fraction_of_n: bool,
+    /// The parameter whose resolved value is multiplied by the value, if any
+    pub multiple_of: Option<String>,
+}
+
+impl ParameterDomain {
+    /// Constructor of a domain of real values
+    pub fn real(name: &str, min: f64, max: f64) -> ParameterDomain {
+        ParameterDomain {
+            name: name.to_string(),
+            min,
+            max,
+            integer: false,
+            fraction_of_n: false,
+            multiple_of: None,
+        }
+    }
+
+    /// Constructor of a domain of integer values
+    pub fn integer(name: &str, min: f64, max: f64) -> ParameterDomain {
+        ParameterDomain {
+            integer: true,
+            ..ParameterDomain::real(name, min, max)
+        }
+    }
+
+    /// Constructor of a domain of integer values expressed
+    /// as fractions of the instance size
+    pub fn fraction_of_n(name: &str, min: f64, max: f64) -> ParameterDomain {
+        ParameterDomain {
+            integer: true,
+            fraction_of_n: true,
+            ..ParameterDomain::real(name, min, max)
+        }
+    }
+
+    /// Constructor of a domain of integer values expressed as multiples
+    /// of the resolved value of another parameter, e.g. the candidate
+    /// list size relative to the tabu tenure
+    pub fn multiple_of(name: &str, base: &str, min: f64, max: f64) -> ParameterDomain {
+        ParameterDomain {
+            integer: true,
+            multiple_of: Some(base.to_string()),
+            ..ParameterDomain::real(name, min, max)
+        }
+    }
+
+    /// Keeps the value within the domain, rounding integers
+    /// which are not relative to n or another parameter
+    pub fn clamp(&self, value: f64) -> f64 {
+        let value = value.clamp(self.min, self.max);
+        if self.integer && !self.fraction_of_n && self.multiple_of.is_none() {
+            value.round()
+        } else {
+            value
+        }
+    }
+
+    /// Draws a value uniformly from the domain
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        if self.min >= self.max {
+            return self.clamp(self.min);
+        }
+        self.clamp(rng.gen_range(self.min..=self.max))
+    }
+
+    /// Returns the value passed to the solver on an instance of size n
+    pub fn resolve(&self, value: f64, n: usize) -> f64 {
+        if self.fraction_of_n {
+            (value * n as f64).round().max(1.0)
+        } else if self.integer {
+            value.round()
+        } else {
+            value
+        }
+    }
+}
+
+/// Returns the parameters passed to the solver on an instance of size n.
+/// Multiples of other parameters are resolved last, truncated to integers
+/// of at least 1.
+pub fn resolve_parameters(domains: &[ParameterDomain], configuration: &Parameters, n: usize) -> Parameters {
+    let mut parameters = configuration.clone();
+    for domain in domains.iter().filter(|d| d.multiple_of.is_none()) {
+        if let Some(value) = configuration.get(&domain.name) {
+            parameters.insert(domain.name.clone(), domain.resolve(*value, n));
+        }
+    }
+    for domain in domains {
+        if let (Some(base), Some(value)) = (&domain.multiple_of, configuration.get(&domain.name)) {
+            let base_value = parameters.get(base).copied().unwrap_or(1.0);
+            parameters.insert(domain.name.clone(), (value * base_value).floor().max(1.0));
+        }
+    }
+    parameters
+}
+
+/// Returns the parameter domains used to tune a solver
+pub fn default_domains(solver: &str) -> Vec<ParameterDomain> {
+    match solver {
+        "tabu" => vec![
+            ParameterDomain::fraction_of_n("tenure", 0.05, 0.5),
+            ParameterDomain::fraction_of_n("k", 0.05, 1.0),
+            ParameterDomain::integer("lack_impr_iter", 20.0, 500.0),
+            ParameterDomain::real("cn_ratio", 0.3, 1.0),
+        ],
+        "sa" => vec![
+            ParameterDomain::real("temp_mul", 0.8, 0.99),
+            ParameterDomain::integer("iter_mul", 2.0, 20.0),
+            ParameterDomain::integer("l_div", 1.0, 8.0),
+        ],
+        _ => vec![],
+    }
+}
+
+/// Returns the class of an instance derived from its file name:
+/// the family prefix, followed by the variant letter for Taillard
+/// instances, e.g. "tai60a.dat" -> "tai-a", "nug30.dat" -> "nug"
+pub fn instance_class(filename: &str) -> String {
+    let family = instance_family(filename);
+    let variant = instance_variant(filename);
+    if family == "tai" && !variant.is_empty() {
+        format!("{}-{}", family, variant)
+    } else {
+        family
+    }
+}
+
+/// Saves configurations in the json format read by the experiment
+/// binaries: {instance file name: {parameter: value}}. Integer
+/// parameters are written as json integers.
+pub fn save_configs(
+    configs: &BTreeMap<String, Parameters>,
+    domains: &[ParameterDomain],
+    path: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut json = Map::new();
+    for (instance, parameters) in configs {
+        let mut params = Map::new();
+        for (name, value) in parameters {
+            let is_integer = domains.iter().any(|d| &d.name == name && d.integer);
+            let number = if is_integer {
+                Number::from(value.round() as i64)
+            } else {
+                Number::from_f64(*value).ok_or("Parameter value is not finite")?
+            };
+            params.insert(name.clone(), Value::Number(number));
+        }
+        json.insert(instance.clone(), Value::Object(params));
+    }
+    let file = File::create(path)?;
+    serde_json::to_writer_pretty(file, &Value::Object(json))?;
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use rand::rngs::ThreadRng;
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::qap_problem::QapProblem;
+use crate::solvers::registry::{build_solver, Parameters};
+use crate::stats::{friedman_test, nemenyi_critical_difference};
+use crate::tuning::parameters::{instance_class, resolve_parameters, ParameterDomain};
+
+/// Settings of the iterated racing procedure
+#[derive(Debug, Clone)]
+pub struct RaceSettings {
+    /// The number of configurations raced in every iteration
+    pub n_configurations: usize,
+    /// The total number of solver runs
+    pub budget: usize,
+    /// The number of iterations of sampling and racing
+    pub n_iterations: usize,
+    /// The number of blocks (instance runs) before the first elimination test
+    pub first_test: usize,
+    /// The number of best configurations kept for the next iteration
+    pub n_elites: usize,
+    /// Significance level of the Friedman test
+    pub alpha: f64,
+}
+
+impl Default for RaceSettings {
+    fn default() -> Self {
+        RaceSettings {
+            n_configurations: 20,
+            budget: 500,
+            n_iterations: 3,
+            first_test: 5,
+            n_elites: 3,
+            alpha: 0.05,
+        }
+    }
+}
+
+/// Iterated racing (irace-style) of solver configurations on a set of
+/// instances. Configurations are sampled from the parameter domains,
+/// evaluated instance by instance, and eliminated as soon as the Friedman
+/// test with the Nemenyi post-hoc shows they are worse than the best one.
+/// The next iteration samples around the surviving elite configurations.
+pub struct Race {
+    solver: String,
+    domains: Vec<ParameterDomain>,
+    problems: Vec<QapProblem>,
+    settings: RaceSettings,
+    rng: ThreadRng,
+    /// The number of solver runs performed so far
+    runs: usize,
+}
+
+impl Race {
+    /// Constructor, reads the instances
+    pub fn new(
+        solver: &str,
+        domains: Vec<ParameterDomain>,
+        instance_paths: &[String],
+        settings: RaceSettings,
+    ) -> Result<Race, std::io::Error> {
+        let mut problems = Vec::with_capacity(instance_paths.len());
+        for path in instance_paths {
+            problems.push(QapProblem::new(path)?);
+        }
+        Ok(Race {
+            solver: solver.to_string(),
+            domains,
+            problems,
+            settings,
+            rng: rand::thread_rng(),
+            runs: 0,
+        })
+    }
+
+    /// Returns the number of solver runs performed so far
+    pub fn get_run_count(&self) -> usize {
+        self.runs
+    }
+
+    /// Draws a configuration uniformly from the domains
+    fn sample_uniform(&mut self) -> Parameters {
+        let mut configuration = Parameters::new();
+        for domain in &self.domains {
+            configuration.insert(domain.name.clone(), domain.sample(&mut self.rng));
+        }
+        configuration
+    }
+
+    /// Draws a configuration around one of the elites (better elites are
+    /// more likely). The spread shrinks with every iteration.
+    fn sample_around(&mut self, elites: &[Parameters], iteration: usize) -> Parameters {
+        let weights: Vec<usize> = (1..=elites.len()).rev().collect();
+        let mut choice = self.rng.gen_range(0..weights.iter().sum::<usize>());
+        let mut elite = &elites[0];
+        for (i, &weight) in weights.iter().enumerate() {
+            if choice < weight {
+                elite = &elites[i];
+                break;
+            }
+            choice -= weight;
+        }
+        let spread = 0.3 * 0.7_f64.powi(iteration as i32 - 1);
+        let mut configuration = Parameters::new();
+        for domain in &self.domains {
+            let mean = elite[&domain.name];
+            // Box-Muller transform
+            let u1: f64 = self.rng.gen_range(f64::EPSILON..1.0);
+            let u2: f64 = self.rng.gen_range(0.0..1.0);
+            let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+            let value = mean + z * spread * (domain.max - domain.min);
+            configuration.insert(domain.name.clone(), domain.clamp(value));
+        }
+        configuration
+    }
+
+    /// Runs the solver with a configuration on an instance
+    /// and returns the final evaluation
+    fn evaluate(&mut self, configuration: &Parameters, instance: usize) -> f64 {
+        let problem = &mut self.problems[instance];
+        let parameters = resolve_parameters(&self.domains, configuration, problem.get_n());
+        let mut solver = build_solver(&self.solver, problem, &parameters)
+            .expect("Tuned parameters should be accepted by the solver");
+        self.runs += 1;
+        solver.solve().get_eval() as f64
+    }
+
+    /// Races the candidates using at most `budget` solver runs.
+    /// Returns the surviving candidates sorted by average rank.
+    fn race(&mut self, candidates: Vec<Parameters>, budget: usize) -> Vec<Parameters> {
+        if candidates.len() < 2 {
+            return candidates;
+        }
+        let mut alive: Vec<usize> = (0..candidates.len()).collect();
+        // results[c][b] is the evaluation of candidate c on block b
+        let mut results: Vec<Vec<f64>> = vec![vec![]; candidates.len()];
+        let mut spent = 0;
+        let mut block = 0;
+        while alive.len() > 1 && (block == 0 || spent + alive.len() <= budget) {
+            let instance = block % self.problems.len();
+            for &c in &alive {
+                let value = self.evaluate(&candidates[c], instance);
+                results[c].push(value);
+            }
+            spent += alive.len();
+            block += 1;
+
+            if block >= self.settings.first_test {
+                let (test, ranks) = friedman_test(&block_matrix(&results, &alive, block));
+                if test.p_value < self.settings.alpha {
+                    let critical_difference =
+                        nemenyi_critical_difference(alive.len(), block, self.settings.alpha);
+                    let best_rank = ranks.iter().copied().fold(f64::INFINITY, f64::min);
+                    alive = alive
+                        .iter()
+                        .zip(&ranks)
+                        .filter(|(_, &rank)| rank - best_rank <= critical_difference)
+                        .map(|(&c, _)| c)
+                        .collect();
+                    println!("Block {}: {} configurations alive", block, alive.len());
+                }
+            }
+        }
+        // Sort the survivors by average rank
+        let (_, ranks) = friedman_test(&block_matrix(&results, &alive, block));
+        let mut order: Vec<usize> = (0..alive.len()).collect();
+        order.sort_by(|&a, &b| ranks[a].partial_cmp(&ranks[b]).unwrap());
+        order.iter().map(|&i| candidates[alive[i]].clone()).collect()
+    }
+
+    /// Runs the iterated race and returns the elite configurations,
+    /// the best first
+    pub fn run(&mut self) -> Vec<Parameters> {
+        let settings = self.settings.clone();
+        let mut elites: Vec<Parameters> = vec![];
+        for iteration in 0..settings.n_iterations {
+            let remaining_budget = settings.budget.saturating_sub(self.runs);
+            let budget = remaining_budget / (settings.n_iterations - iteration);
+            if budget == 0 {
+                break;
+            }
+            let mut candidates = elites.clone();
+            while candidates.len() < settings.n_configurations.max(elites.len() + 1) {
+                let candidate = if elites.is_empty() {
+                    self.sample_uniform()
+                } else {
+                    self.sample_around(&elites, iteration)
+                };
+                candidates.push(candidate);
+            }
+            println!("Iteration {}: racing {} configurations", iteration, candidates.len());
+            elites = self.race(candidates, budget);
+            elites.truncate(settings.n_elites);
+        }
+        elites
+    }
+}
+
+/// Returns a matrix with a row per block and a column per alive candidate
+fn block_matrix(results: &[Vec<f64>], alive: &[usize], n_blocks: usize) -> Vec<Vec<f64>> {
+    (0..n_blocks)
+        .map(|b| alive.iter().map(|&c| results[c][b]).collect())
+        .collect()
+}
+
+/// Races configurations of a solver separately for every instance class
+/// and returns, for every instance file name, the parameters of the best
+/// configuration of its class resolved for the instance size
+pub fn tune_by_class(
+    solver: &str,
+    domains: &[ParameterDomain],
+    instance_paths: &[String],
+    settings: &RaceSettings,
+) -> Result<BTreeMap<String, Parameters>, std::io::Error> {
+    let mut classes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for path in instance_paths {
+        let filename = file_name(path);
+        classes.entry(instance_class(&filename)).or_default().push(path.clone());
+    }
+    let mut configs = BTreeMap::new();
+    for (class, paths) in classes {
+        println!("Tuning {} on class {} ({} instances)", solver, class, paths.len());
+        let mut race = Race::new(solver, domains.to_vec(), &paths, settings.clone())?;
+        let elites = race.run();
+        let best = match elites.first() {
+            Some(best) => best.clone(),
+            None => continue,
+        };
+        println!("Best configuration of class {}: {:?}", class, best);
+        for (path, problem) in paths.iter().zip(&race.problems) {
+            let parameters = resolve_parameters(domains, &best, problem.get_n());
+            configs.insert(file_name(path), parameters);
+        }
+    }
+    Ok(configs)
+}
+
+/// Returns the file name of a path
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
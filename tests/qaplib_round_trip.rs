@@ -0,0 +1,65 @@
+use qap_local_search::qap_problem::QapProblem;
+use qap_local_search::solution::Solution;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Folder with the bundled instances
+const DATA_FOLDER: &str = "data/qapdatsol";
+
+/// Returns the paths of the bundled files with the given extension
+fn bundled_files(extension: &str) -> Vec<PathBuf> {
+    let folder = Path::new(env!("CARGO_MANIFEST_DIR")).join(DATA_FOLDER);
+    let mut paths: Vec<PathBuf> = fs::read_dir(folder)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == extension))
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Returns a path in a fresh temporary folder of the test
+fn temporary_path(test: &str, file_name: &str) -> String {
+    let folder = std::env::temp_dir().join(format!("qap_round_trip_{}_{}", test, std::process::id()));
+    fs::create_dir_all(&folder).unwrap();
+    folder.join(file_name).to_string_lossy().to_string()
+}
+
+#[test]
+fn instances_round_trip() {
+    let paths = bundled_files("dat");
+    assert!(!paths.is_empty());
+    for path in paths {
+        let problem = QapProblem::new(&path.to_string_lossy()).unwrap();
+        let n = problem.get_n();
+        for matrix in [problem.matrix_a_ref(), problem.matrix_b_ref()] {
+            assert_eq!(matrix.len(), n, "{:?}", path);
+            assert!(matrix.iter().all(|row| row.len() == n), "{:?}", path);
+        }
+
+        let copy_path = temporary_path("dat", &path.file_name().unwrap().to_string_lossy());
+        problem.write_qaplib(&copy_path).unwrap();
+        let copy = QapProblem::new(&copy_path).unwrap();
+        assert_eq!(copy.get_n(), n, "{:?}", path);
+        assert_eq!(copy.matrix_a_ref(), problem.matrix_a_ref(), "{:?}", path);
+        assert_eq!(copy.matrix_b_ref(), problem.matrix_b_ref(), "{:?}", path);
+        fs::remove_file(copy_path).unwrap();
+    }
+}
+
+#[test]
+fn solutions_round_trip() {
+    let paths = bundled_files("sln");
+    assert!(!paths.is_empty());
+    for path in paths {
+        let solution = Solution::read_sln(&path.to_string_lossy()).unwrap();
+
+        let copy_path = temporary_path("sln", &path.file_name().unwrap().to_string_lossy());
+        solution.write_sln(&copy_path).unwrap();
+        let copy = Solution::read_sln(&copy_path).unwrap();
+        assert_eq!(copy.solution_array, solution.solution_array, "{:?}", path);
+        assert_eq!(copy.get_eval(), solution.get_eval(), "{:?}", path);
+        fs::remove_file(copy_path).unwrap();
+    }
+}